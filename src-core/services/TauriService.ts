@@ -81,11 +81,19 @@ export type GameProgressDownload = {
  * @property {string} name - Nom du fichier
  * @property {string} hash - Hash du fichier
  * @property {number} size - Taille du fichier en octets
+ * @property {number} mode - Mode Unix du fichier (ex: 0o755), prioritaire sur executable
+ * @property {boolean} executable - Fichier exécutable sous Unix (mode 0o755)
+ * @property {string} component - Composant optionnel auquel appartient le fichier, absent pour le jeu de base
+ * @property {string[]} locales - Langues du fichier (ex: "fr-FR"), absent pour un fichier commun à toutes les langues
  */
 export type FileDetails = {
   name: string
   hash: string
   size: number
+  mode?: number
+  executable?: boolean
+  component?: string
+  locales?: string[]
 }
 
 /**
@@ -98,6 +106,13 @@ export type FileDetails = {
  * @property {number} gameBinarySize - Taille du jeu
  * @property {string} version - Version du jeu (ex: v1.0.0)
  * @property {FileDetails[]} files - Liste des fichiers de la version du jeu
+ * @property {string[]} launchArguments - Arguments de lancement par défaut fournis par le serveur
+ * @property {string | null} executable - Exécutable principal déclaré pour cette plateforme (relatif au dossier du jeu)
+ * @property {boolean} allowMultipleInstances - Le jeu peut être lancé plusieurs fois en même temps
+ * @property {string[]} preservedPaths - Données du joueur à ne jamais supprimer, motifs glob (ex: "saves/**", "*.ini")
+ * @property {ReleaseChannel} channel - Canal de version installé
+ * @property {string[] | null} components - Composants optionnels installés, null si le joueur n'a jamais choisi
+ * @property {string[] | null} languages - Langues installées, null si toutes les langues sont installées
  */
 export type GameManifestLocal = {
   pathInstallLocation: string
@@ -106,6 +121,23 @@ export type GameManifestLocal = {
   gameBinarySize: number
   version: string
   files: FileDetails[]
+  launchArguments?: string[]
+  executable?: string | null
+  allowMultipleInstances?: boolean
+  preservedPaths?: string[]
+  channel?: ReleaseChannel
+  components?: string[] | null
+  languages?: string[] | null
+}
+
+/**
+ * Canal de version d'un jeu
+ * @property {string} name - "stable", "beta" ou nom d'une branche
+ * @property {string} accessKey - Clé d'accès des branches privées, envoyée au launcher qui la garde dans ses données (jamais renvoyée)
+ */
+export type ReleaseChannel = {
+  name: string
+  accessKey?: string
 }
 
 /**
@@ -113,10 +145,289 @@ export type GameManifestLocal = {
  * @type {object} GameManifestRemote
  * @property {string} version - Version du jeu (ex: v1.0.0)
  * @property {FileDetails[]} files - Liste des fichiers de la version du jeu
+ * @property {string[]} launchArguments - Arguments de lancement par défaut du jeu
+ * @property {ExecutableDeclaration[]} executables - Exécutables principaux du jeu par OS / architecture
+ * @property {boolean} allowMultipleInstances - Le jeu peut être lancé plusieurs fois en même temps
+ * @property {string[]} preservedPaths - Données du joueur à ne jamais supprimer, motifs glob (ex: "saves/**", "*.ini")
+ * @property {GameComponent[]} components - Composants optionnels du jeu (textures HD, packs de langue, DLC)
+ * @property {string | null} defaultLanguage - Langue installée si le joueur n'en choisit pas, toutes si absente
  */
 export type GameManifestRemote = {
   version: string
   files: FileDetails[]
+  launchArguments?: string[]
+  executables?: ExecutableDeclaration[]
+  allowMultipleInstances?: boolean
+  preservedPaths?: string[]
+  components?: GameComponent[]
+  defaultLanguage?: string | null
+}
+
+/**
+ * Composant optionnel d'un jeu déclaré par le manifest.json
+ * @property {string} id - Identifiant du composant (référencé par FileDetails.component)
+ * @property {string} name - Nom affiché
+ * @property {string[]} dependencies - Composants installés avec celui-ci
+ * @property {boolean} default - Installé si le joueur ne choisit pas ses composants
+ */
+export type GameComponent = {
+  id: string
+  name: string
+  dependencies?: string[]
+  default?: boolean
+}
+
+/**
+ * Déclaration de l'exécutable principal d'un jeu pour un OS
+ * @property {string} os - Système d'exploitation (Windows, macOS, Linux)
+ * @property {string | null} architecture - Architecture (x64, arm64), toutes si absente
+ * @property {string} path - Chemin de l'exécutable relatif au dossier du jeu
+ */
+export type ExecutableDeclaration = {
+  os: string
+  architecture?: string | null
+  path: string
+}
+
+/**
+ * Options de lancement d'un jeu configurées par le joueur
+ * @property {string[]} arguments - Arguments ajoutés à ceux du manifest
+ * @property {Record<string, string>} environmentVariables - Variables d'environnement supplémentaires
+ * @property {string | null} workingDirectory - Dossier de travail (relatif au dossier du jeu ou absolu)
+ */
+export type LaunchOptions = {
+  arguments: string[]
+  environmentVariables: Record<string, string>
+  workingDirectory?: string | null
+}
+
+/**
+ * Résultat de la vérification d'un jeu avant son lancement
+ * - ok : le jeu peut être lancé
+ * - updateRequired : la version locale est différente de celle du serveur
+ * - repairRequired : des fichiers sont manquants ou n'ont pas la bonne taille
+ * - alreadyRunning : le jeu est déjà lancé et n'autorise pas plusieurs instances
+ */
+export type PreLaunchStatus =
+  | { status: 'ok' }
+  | { status: 'updateRequired'; localVersion: string; remoteVersion: string }
+  | { status: 'repairRequired'; missingFiles: FileDetails[]; sizeMismatchFiles: FileDetails[] }
+  | { status: 'alreadyRunning'; gameId: number }
+
+/**
+ * Comportement de la fenêtre du launcher pendant qu'un jeu est lancé
+ * - none : la fenêtre reste affichée
+ * - minimize : la fenêtre est réduite puis restaurée à la fermeture du jeu
+ * - hide : la fenêtre est cachée (barre d'état) puis restaurée à la fermeture du jeu
+ */
+export type LaunchWindowBehavior = 'none' | 'minimize' | 'hide'
+
+/**
+ * Paramètres du launcher gérés par le backend
+ * @property {LaunchWindowBehavior} launchWindowBehavior - Comportement de la fenêtre au lancement d'un jeu
+ * @property {boolean} keepPreviousVersion - Garder les fichiers remplacés par la dernière mise à jour
+ * @property {number} previousVersionMaxSize - Taille maximale (en octets) de la version précédente gardée
+ */
+export type LauncherSettings = {
+  launchWindowBehavior: LaunchWindowBehavior
+  keepPreviousVersion: boolean
+  previousVersionMaxSize: number
+}
+
+/**
+ * Demande reçue par une URL crzgames:// (ex: crzgames://launch/42) ou des arguments du launcher,
+ * émise avec l'event 'deep-link-request'
+ */
+export type DeepLinkRequest = { action: 'launch'; gameId: number } | { action: 'install'; gameId: number }
+
+/**
+ * Fichier d'un raccourci créé par le launcher pour un jeu
+ * @property {string} path - Chemin du fichier (raccourci, fichier .desktop, icône)
+ * @property {boolean} exists - Le fichier existe toujours sur le disque
+ */
+export type ShortcutStatus = {
+  path: string
+  exists: boolean
+}
+
+/**
+ * Raccourcis choisis pour un jeu, enregistrés par le launcher
+ * @property {boolean} desktop - Raccourci sur le bureau
+ * @property {boolean} applicationsMenu - Entrée du menu Démarrer (Windows), ~/Applications (macOS) ou du menu des applications (Linux)
+ * @property {boolean} taskbarPin - Épingler au dock (GNOME uniquement, ignoré sur Windows et macOS)
+ */
+export type ShortcutOptions = {
+  desktop: boolean
+  applicationsMenu: boolean
+  taskbarPin: boolean
+}
+
+/**
+ * Résultat de la désinstallation d'un jeu, chemins relatifs au dossier du jeu
+ * @property {string[]} removedFiles - Fichiers supprimés (fichiers du manifest et du launcher)
+ * @property {string[]} leftoverFiles - Fichiers ajoutés par l'utilisateur, laissés sur le disque
+ * @property {string[]} preservedFiles - Données du joueur conservées (preservedPaths du manifest)
+ * @property {boolean} directoryRemoved - Le dossier du jeu a été supprimé car il était vide
+ */
+export type UninstallReport = {
+  removedFiles: string[]
+  leftoverFiles: string[]
+  preservedFiles: string[]
+  directoryRemoved: boolean
+}
+
+/**
+ * Résultat de l'import d'un dossier de jeu existant
+ * @property {string[]} matchedFiles - Fichiers déjà présents et valides (hash identique au manifest distant)
+ * @property {FileDetails[]} filesToDownload - Fichiers qui restent à télécharger
+ * @property {number} sizeToDownload - Taille des fichiers restant à télécharger
+ */
+export type ImportReport = {
+  matchedFiles: string[]
+  filesToDownload: FileDetails[]
+  sizeToDownload: number
+}
+
+/**
+ * Jeu de la bibliothèque tenue par le launcher
+ * @property {number} gameId - Identifiant du jeu
+ * @property {string} gameTitle - Titre du jeu
+ * @property {string} pathInstallLocation - Emplacement d'installation du jeu
+ * @property {string} version - Version installée
+ * @property {number} gameBinarySize - Taille du jeu
+ * @property {number} installedAt - Date d'installation (timestamp Unix en secondes)
+ */
+export type InstalledGame = {
+  gameId: number
+  gameTitle: string
+  pathInstallLocation: string
+  version: string
+  gameBinarySize: number
+  installedAt: number
+}
+
+/**
+ * Résultat de la vérification de la bibliothèque
+ * @property {InstalledGame[]} installedGames - Jeux toujours installés
+ * @property {InstalledGame[]} removedGames - Jeux supprimés en dehors du launcher, retirés de la bibliothèque
+ */
+export type LibraryRescanReport = {
+  installedGames: InstalledGame[]
+  removedGames: InstalledGame[]
+}
+
+/**
+ * Dossier de bibliothèque où sont installés les jeux
+ * @property {string} path - Chemin du dossier
+ * @property {boolean} configured - false pour le dossier par défaut, utilisé tant qu'aucun dossier n'est configuré
+ * @property {number | null} totalSpace - Taille du disque qui contient le dossier
+ * @property {number | null} availableSpace - Espace libre sur ce disque
+ * @property {number[]} gameIds - Jeux installés dans ce dossier
+ */
+export type LibraryFolder = {
+  path: string
+  configured: boolean
+  totalSpace: number | null
+  availableSpace: number | null
+  gameIds: number[]
+}
+
+/**
+ * Disque qui contient un chemin
+ * @property {string} mountPoint - Point de montage du disque
+ * @property {string} fileSystem - Système de fichiers (ex: NTFS, ext4, apfs)
+ * @property {number} totalSpace - Taille du disque
+ * @property {number} availableSpace - Espace libre sur le disque
+ */
+export type DiskSpaceInfo = {
+  mountPoint: string
+  fileSystem: string
+  totalSpace: number
+  availableSpace: number
+}
+
+/**
+ * Version précédente gardée après une mise à jour
+ * @property {string} version - Version précédente du jeu
+ * @property {number} size - Taille des fichiers gardés
+ */
+export type PreviousVersionInfo = {
+  version: string
+  size: number
+}
+
+/**
+ * Différence entre la version installée et la version cible d'un canal
+ * @property {ReleaseChannel} currentChannel - Canal installé
+ * @property {string} currentVersion - Version installée
+ * @property {string} targetVersion - Version cible
+ * @property {FileDetails[]} filesToDownload - Fichiers nouveaux, modifiés ou manquants
+ * @property {number} sizeToDownload - Taille à télécharger
+ * @property {string[]} obsoleteFiles - Fichiers installés absents de la version cible
+ * @property {number} sizeToRemove - Taille des fichiers supprimés
+ */
+export type ChannelDiff = {
+  currentChannel: ReleaseChannel
+  currentVersion: string
+  targetVersion: string
+  filesToDownload: FileDetails[]
+  sizeToDownload: number
+  obsoleteFiles: string[]
+  sizeToRemove: number
+}
+
+/**
+ * Composant optionnel d'un jeu avec sa taille
+ * @property {string} id - Identifiant du composant
+ * @property {string} name - Nom affiché
+ * @property {string[]} dependencies - Composants installés avec celui-ci
+ * @property {boolean} default - Installé si le joueur ne choisit pas ses composants
+ * @property {number} size - Taille des fichiers du composant
+ * @property {boolean} installed - Composant installé
+ */
+export type GameComponentInfo = {
+  id: string
+  name: string
+  dependencies: string[]
+  default: boolean
+  size: number
+  installed: boolean
+}
+
+/**
+ * Fichiers à télécharger pour installer un composant
+ * @property {string[]} components - Composants à passer à downloadGame (dépendances comprises)
+ * @property {FileDetails[]} filesToDownload - Fichiers à télécharger
+ * @property {number} sizeToDownload - Taille à télécharger
+ */
+export type ComponentChange = {
+  components: string[]
+  filesToDownload: FileDetails[]
+  sizeToDownload: number
+}
+
+/**
+ * Taille des fichiers propres à une langue
+ * @property {string} language - Langue (ex: "fr-FR")
+ * @property {number} size - Taille des fichiers de la langue
+ * @property {boolean} default - Langue installée par défaut
+ */
+export type LanguageSize = {
+  language: string
+  size: number
+  default: boolean
+}
+
+/**
+ * Fichiers à télécharger après un changement de langues
+ * @property {string[] | null} languages - Langues à passer à downloadGame, null si toutes les langues sont installées
+ * @property {FileDetails[]} filesToDownload - Fichiers des nouvelles langues à télécharger
+ * @property {number} sizeToDownload - Taille à télécharger
+ */
+export type LanguageChange = {
+  languages: string[] | null
+  filesToDownload: FileDetails[]
+  sizeToDownload: number
 }
 
 /**
@@ -145,21 +456,8 @@ export class TauriService {
     try {
       if (!pathInstallLocation) throw new Error('Installation path not provided')
 
-      const systemOSInfo: SystemOSInfo | undefined = await this.getSystemOSCurrent()
-      if (!systemOSInfo) throw new Error('Failed to get system OS info')
-
-      let mountPoint: string | undefined
-
-      if (systemOSInfo.os.toLowerCase().includes('windows')) {
-        const match: RegExpMatchArray | null = pathInstallLocation.match(/^[a-zA-Z]:\\/)
-        if (match) mountPoint = match[0] // For Windows, get the drive letter
-      } else {
-        mountPoint = '/' // For macOS/Linux, use root as the mount point
-      }
-
-      if (!mountPoint) throw new Error(`Could not determine mount point for path: ${pathInstallLocation}`)
-
-      const freeSpace: unknown = await invoke('check_disk_space', { path: mountPoint })
+      // Le disque est résolu côté Rust, même si le dossier du jeu n'existe pas encore
+      const freeSpace: unknown = await invoke('check_disk_space', { path: pathInstallLocation })
 
       return {
         pathSystem: pathInstallLocation,
@@ -182,28 +480,12 @@ export class TauriService {
         defaultPath: await appConfigDir(),
       })
 
-      const systemOSInfo: SystemOSInfo | undefined = await this.getSystemOSCurrent()
-      if (!systemOSInfo) throw new Error('Failed to get system OS info')
-
-      let mountPoint: string | undefined = undefined
-
-      if (typeof selectedPath === 'string') {
-        if (systemOSInfo.os.toLowerCase().includes('windows')) {
-          const match: RegExpMatchArray | null = selectedPath.match(/^[a-zA-Z]:\\/)
-          if (match) {
-            mountPoint = match[0] // For Windows, get the drive letter
-          }
-        } else {
-          mountPoint = '/' // For macOS/Linux, use root as the mount point
-        }
-      } else {
+      if (typeof selectedPath !== 'string') {
         throw new Error(`Invalid selectedPath: ${selectedPath}`)
       }
 
-      if (!mountPoint) throw new Error(`Could not determine mount point for selected path: ${selectedPath}`)
-
       console.log('Selected directory for install game :', selectedPath)
-      const freeSpace: unknown = await invoke('check_disk_space', { path: mountPoint })
+      const freeSpace: unknown = await invoke('check_disk_space', { path: selectedPath })
       console.log(`Espace libre sur le disque: ${freeSpace} bytes`)
 
       return {
@@ -215,6 +497,23 @@ export class TauriService {
     }
   }
 
+  /**
+   * Récupère le dossier de bibliothèque conseillé pour une nouvelle installation
+   * @returns {Promise<PathInstallLocation | undefined>} - Dossier avec le plus d'espace libre
+   */
+  public static async getDefaultInstallLocation(): Promise<PathInstallLocation | undefined> {
+    try {
+      const libraryFolder: LibraryFolder = await invoke('suggest_library_folder')
+
+      return {
+        pathSystem: libraryFolder.path,
+        diskFreeSpace: libraryFolder.availableSpace ?? undefined,
+      } as PathInstallLocation
+    } catch (error) {
+      console.error('getDefaultInstallLocation Error:', error)
+    }
+  }
+
   /**
    * Récupère le chemin de l'exécutable du launcher lui-même
    * @returns {Promise<PathInstallLocation | undefined>} - Emplacement de l'exécutable
@@ -284,6 +583,9 @@ export class TauriService {
    * @param {number} userId - Identifiant de l'utilisateur
    * @param {FileDetails[]} filesToDownload - Liste des fichiers à télécharger
    * @param {GameManifestRemote} gameManifestRemote - Fichier manifest.json du jeu côté serveur
+   * @param {boolean} shortcutThroughLauncher - Le raccourci lance le jeu via le launcher (mises à jour, vérification)
+   * @param {ShortcutOptions | undefined} shortcutOptions - Raccourcis à créer, remplace desktopShortcut s'il est fourni
+   * @param {boolean} stagedUpdate - Mise à jour préparée à part : la version installée reste jouable jusqu'à la bascule
    * @returns {Promise<void>} - Promesse résolue
    */
   public static async downloadGame(
@@ -298,6 +600,12 @@ export class TauriService {
     userId: number,
     filesToDownload: FileDetails[],
     gameManifestRemote: GameManifestRemote,
+    shortcutThroughLauncher: boolean = false,
+    shortcutOptions?: ShortcutOptions,
+    stagedUpdate: boolean = false,
+    releaseChannel?: ReleaseChannel,
+    components?: string[],
+    languages?: string[],
   ): Promise<void> {
     try {
       const userSystemOSInfo: SystemOSInfo | undefined = await this.getSystemOSCurrent()
@@ -327,6 +635,12 @@ export class TauriService {
           fileLocationDownload,
           filesToDownload,
           desktopShortcut,
+          shortcutThroughLauncher,
+          shortcutOptions,
+          stagedUpdate,
+          releaseChannel,
+          components,
+          languages,
           gameTitle,
           gameVersion,
           gameBinarySize,
@@ -345,11 +659,17 @@ export class TauriService {
   /**
    * Lance le jeu
    * @param {string | undefined} pathFileSystem - Emplacement du fichier système
-   * @returns {Promise<void>} - Promesse résolue
+   * @param {boolean} verifyFiles - Vérifie les fichiers manquants et leur taille avant le lancement
+   * @param {string | undefined} remoteVersion - Version du jeu côté serveur à comparer avec la version locale
+   * @returns {Promise<PreLaunchStatus>} - Statut de la vérification, le jeu n'est lancé que si 'ok'
    */
-  public static async launchGame(pathFileSystem: string | undefined): Promise<void> {
+  public static async launchGame(
+    pathFileSystem: string | undefined,
+    verifyFiles: boolean = false,
+    remoteVersion?: string,
+  ): Promise<PreLaunchStatus> {
     try {
-      await invoke('launch_game', { fileLocationDownload: pathFileSystem })
+      return await invoke('launch_game', { fileLocationDownload: pathFileSystem, verifyFiles, remoteVersion })
     } catch (error) {
       console.error('launchGame error : ', error)
       throw error
@@ -948,11 +1268,17 @@ export class TauriService {
   /**
    * Crée un raccourci sur le bureau pour le jeu installé
    * @param {string} pathInstallLocationGame - Chemin d'installation du jeu
+   * @param {boolean} throughLauncher - Le raccourci lance le jeu via le launcher au lieu de l'exécutable
+   * @param {ShortcutOptions | undefined} options - Raccourcis à créer (par défaut le bureau, et le menu des applications sur Linux)
    * @returns {Promise<void>} - Promesse résolue
    */
-  public static async createShortcutOnDesktop(pathInstallLocationGame: string): Promise<void> {
+  public static async createShortcutOnDesktop(
+    pathInstallLocationGame: string,
+    throughLauncher: boolean = false,
+    options?: ShortcutOptions,
+  ): Promise<void> {
     try {
-      await invoke('create_shortcut', { directoryPath: pathInstallLocationGame })
+      await invoke('create_shortcut', { directoryPath: pathInstallLocationGame, throughLauncher, options })
     } catch (error) {
       console.error('Create shortcut on desktop error : ', error)
       throw error
@@ -1004,15 +1330,523 @@ export class TauriService {
   }
 
   /**
-   * Désinstalle le jeu
+   * Désinstalle le jeu : seuls les fichiers installés par le launcher sont supprimés
+   * @param {string} pathInstallLocation - Le chemin d'installation du jeu (doit contenir manifest_local.json)
+   * @param {boolean} deletePreservedFiles - Supprimer aussi les données du joueur (sauvegardes, configurations)
+   * @returns {Promise<UninstallReport>} - Fichiers supprimés et fichiers laissés en place
+   */
+  public static async uninstallGame(
+    pathInstallLocation: string,
+    deletePreservedFiles: boolean = false,
+  ): Promise<UninstallReport> {
+    try {
+      return await invoke('uninstall_game', { pathInstallLocation, deletePreservedFiles })
+    } catch (error) {
+      console.error('uninstallGame error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère les options de lancement du jeu configurées par le joueur
+   * @param {number} gameId - L'ID du jeu
+   * @returns {Promise<LaunchOptions>} - Options de lancement du jeu
+   */
+  public static async getLaunchOptions(gameId: number): Promise<LaunchOptions> {
+    try {
+      return await invoke('get_launch_options', { gameId })
+    } catch (error) {
+      console.error('getLaunchOptions error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Enregistre les options de lancement du jeu
+   * @param {number} gameId - L'ID du jeu
+   * @param {LaunchOptions} launchOptions - Options de lancement du jeu
+   * @returns {Promise<void>} - Promesse résolue
+   */
+  public static async setLaunchOptions(gameId: number, launchOptions: LaunchOptions): Promise<void> {
+    try {
+      await invoke('set_launch_options', { gameId, launchOptions })
+    } catch (error) {
+      console.error('setLaunchOptions error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Réinitialise les options de lancement du jeu
+   * @param {number} gameId - L'ID du jeu
+   * @returns {Promise<void>} - Promesse résolue
+   */
+  public static async resetLaunchOptions(gameId: number): Promise<void> {
+    try {
+      await invoke('reset_launch_options', { gameId })
+    } catch (error) {
+      console.error('resetLaunchOptions error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Vérifie le jeu avant son lancement (fichiers manquants, tailles et version)
    * @param {string} pathInstallLocation - Le chemin d'installation du jeu
+   * @param {string | undefined} remoteVersion - Version du jeu côté serveur
+   * @returns {Promise<PreLaunchStatus>} - Statut de la vérification
+   */
+  public static async verifyGameBeforeLaunch(
+    pathInstallLocation: string,
+    remoteVersion?: string,
+  ): Promise<PreLaunchStatus> {
+    try {
+      return await invoke('verify_game_before_launch', { fileLocationDownload: pathInstallLocation, remoteVersion })
+    } catch (error) {
+      console.error('verifyGameBeforeLaunch error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Supprime les fichiers d'un jeu installé absents de son manifeste (sauf les données du joueur),
+   * refusé pendant un téléchargement du jeu, les fichiers partiels d'un téléchargement en pause sont gardés
+   * @param {number} gameId - Identifiant du jeu
+   * @returns {Promise<void>}
+   */
+  public static async cleanUpGame(gameId: number): Promise<void> {
+    try {
+      await invoke('clean_up_game', { gameId })
+    } catch (error) {
+      console.error('cleanUpGame error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère les paramètres du launcher
+   * @returns {Promise<LauncherSettings>} - Paramètres du launcher
+   */
+  public static async getLauncherSettings(): Promise<LauncherSettings> {
+    try {
+      return await invoke('get_launcher_settings')
+    } catch (error) {
+      console.error('getLauncherSettings error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Enregistre les paramètres du launcher
+   * @param {LauncherSettings} settings - Paramètres du launcher
    * @returns {Promise<void>} - Promesse résolue
    */
-  public static async uninstallGame(pathInstallLocation: string): Promise<void> {
+  public static async setLauncherSettings(settings: LauncherSettings): Promise<void> {
     try {
-      await invoke('uninstall_game', { pathInstallLocation })
+      await invoke('set_launcher_settings', { settings })
     } catch (error) {
-      console.error('uninstallGame error:', error)
+      console.error('setLauncherSettings error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Arrête le jeu en cours d'exécution
+   * @param {number} gameId - L'ID du jeu
+   * @returns {Promise<void>} - Promesse résolue
+   */
+  public static async stopGame(gameId: number): Promise<void> {
+    try {
+      await invoke('stop_game', { gameId })
+    } catch (error) {
+      console.error('stopGame error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère la demande reçue au démarrage du launcher (URL crzgames:// ou arguments)
+   * @returns {Promise<DeepLinkRequest | null>} - Demande en attente ou null
+   */
+  public static async takePendingDeepLink(): Promise<DeepLinkRequest | null> {
+    try {
+      return await invoke('take_pending_deep_link')
+    } catch (error) {
+      console.error('takePendingDeepLink error:', error)
+      return null
+    }
+  }
+
+  /**
+   * Liste les raccourcis créés par le launcher pour le jeu
+   * @param {number} gameId - L'ID du jeu
+   * @returns {Promise<ShortcutStatus[]>} - Fichiers des raccourcis du jeu
+   */
+  public static async listShortcuts(gameId: number): Promise<ShortcutStatus[]> {
+    try {
+      return await invoke('list_shortcuts', { gameId })
+    } catch (error) {
+      console.error('listShortcuts error:', error)
+      return []
+    }
+  }
+
+  /**
+   * Supprime les raccourcis créés par le launcher pour le jeu
+   * @param {number} gameId - L'ID du jeu
+   * @returns {Promise<string[]>} - Fichiers supprimés
+   */
+  public static async removeShortcuts(gameId: number): Promise<string[]> {
+    try {
+      return await invoke('remove_shortcuts', { gameId })
+    } catch (error) {
+      console.error('removeShortcuts error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Recrée les raccourcis du jeu avec les mêmes options
+   * @param {number} gameId - L'ID du jeu
+   * @returns {Promise<ShortcutStatus[]>} - Fichiers des raccourcis du jeu
+   */
+  public static async repairShortcuts(gameId: number): Promise<ShortcutStatus[]> {
+    try {
+      return await invoke('repair_shortcuts', { gameId })
+    } catch (error) {
+      console.error('repairShortcuts error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère les raccourcis choisis pour le jeu
+   * @param {number} gameId - L'ID du jeu
+   * @returns {Promise<ShortcutOptions | null>} - Options enregistrées, null si aucune
+   */
+  public static async getShortcutOptions(gameId: number): Promise<ShortcutOptions | null> {
+    try {
+      return await invoke('get_shortcut_options', { gameId })
+    } catch (error) {
+      console.error('getShortcutOptions error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Change les raccourcis d'un jeu installé (bureau, menu des applications, dock)
+   * @param {number} gameId - L'ID du jeu
+   * @param {ShortcutOptions} options - Raccourcis à garder ou créer
+   * @param {boolean | undefined} throughLauncher - Lancer via le launcher, par défaut le choix précédent
+   * @returns {Promise<ShortcutStatus[]>} - Fichiers des raccourcis du jeu
+   */
+  public static async setShortcutOptions(
+    gameId: number,
+    options: ShortcutOptions,
+    throughLauncher?: boolean,
+  ): Promise<ShortcutStatus[]> {
+    try {
+      return await invoke('set_shortcut_options', { gameId, options, throughLauncher })
+    } catch (error) {
+      console.error('setShortcutOptions error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Déplace un jeu installé vers un autre dossier ou disque, sans le retélécharger
+   * (events "move-game-progress" et "move-game-complete")
+   * @param {number} gameId - L'ID du jeu
+   * @param {string} newPath - Nouveau dossier du jeu (vide ou inexistant)
+   * @returns {Promise<GameManifestLocal>} - Manifest du jeu avec le nouvel emplacement
+   */
+  public static async moveGameInstallation(gameId: number, newPath: string): Promise<GameManifestLocal> {
+    try {
+      return await invoke('move_game_installation', { gameId, newPath })
+    } catch (error) {
+      console.error('moveGameInstallation error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Adopte un dossier de jeu existant : vérifie les fichiers présents et écrit un nouveau manifest_local.json
+   * (event "import-game-progress"). Le jeu n'est ajouté à la bibliothèque que si aucun fichier ne manque,
+   * sinon à la fin du téléchargement des fichiers restants
+   * @param {string} path - Dossier contenant le jeu
+   * @param {number} gameId - L'ID du jeu
+   * @param {string} gameTitle - Titre du jeu
+   * @param {GameManifestRemote} gameManifestRemote - Fichier manifest.json du jeu côté serveur
+   * @returns {Promise<ImportReport>} - Fichiers valides et fichiers restant à télécharger
+   */
+  public static async importGameInstallation(
+    path: string,
+    gameId: number,
+    gameTitle: string,
+    gameManifestRemote: GameManifestRemote,
+  ): Promise<ImportReport> {
+    try {
+      return await invoke('import_game_installation', { path, gameId, gameTitle, gameManifestRemote })
+    } catch (error) {
+      console.error('importGameInstallation error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère les jeux de la bibliothèque du launcher
+   * @returns {Promise<InstalledGame[]>} - Jeux installés, triés par titre
+   */
+  public static async listInstalledGames(): Promise<InstalledGame[]> {
+    try {
+      return await invoke('list_installed_games')
+    } catch (error) {
+      console.error('listInstalledGames error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Vérifie que les jeux de la bibliothèque sont toujours présents sur le disque
+   * @returns {Promise<LibraryRescanReport>} - Jeux installés et jeux retirés de la bibliothèque
+   */
+  public static async rescanLibrary(): Promise<LibraryRescanReport> {
+    try {
+      return await invoke('rescan_library')
+    } catch (error) {
+      console.error('rescanLibrary error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère les dossiers de bibliothèque avec l'espace disque et les jeux de chacun
+   * @returns {Promise<LibraryFolder[]>} - Dossiers de bibliothèque
+   */
+  public static async listLibraryFolders(): Promise<LibraryFolder[]> {
+    try {
+      return await invoke('list_library_folders')
+    } catch (error) {
+      console.error('listLibraryFolders error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Ajoute un dossier de bibliothèque (créé s'il n'existe pas)
+   * @param {string} path - Chemin du dossier
+   * @returns {Promise<LibraryFolder[]>} - Dossiers de bibliothèque
+   */
+  public static async addLibraryFolder(path: string): Promise<LibraryFolder[]> {
+    try {
+      return await invoke('add_library_folder', { path })
+    } catch (error) {
+      console.error('addLibraryFolder error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Retire un dossier de bibliothèque, les jeux qu'il contient restent installés
+   * @param {string} path - Chemin du dossier
+   * @returns {Promise<LibraryFolder[]>} - Dossiers de bibliothèque
+   */
+  public static async removeLibraryFolder(path: string): Promise<LibraryFolder[]> {
+    try {
+      return await invoke('remove_library_folder', { path })
+    } catch (error) {
+      console.error('removeLibraryFolder error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère l'espace disque du disque qui contient le chemin (qui peut ne pas encore exister)
+   * @param {string} path - Chemin quelconque
+   * @returns {Promise<DiskSpaceInfo>} - Point de montage, système de fichiers et espace disque
+   */
+  public static async getDiskSpace(path: string): Promise<DiskSpaceInfo> {
+    try {
+      return await invoke('get_disk_space', { path })
+    } catch (error) {
+      console.error('getDiskSpace error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère la version précédente gardée par la dernière mise à jour d'un jeu
+   * @param {number} gameId - Identifiant du jeu
+   * @returns {Promise<PreviousVersionInfo | null>} - Version précédente, ou null si aucune n'est gardée
+   */
+  public static async getPreviousVersion(gameId: number): Promise<PreviousVersionInfo | null> {
+    try {
+      return await invoke('get_previous_version', { gameId })
+    } catch (error) {
+      console.error('getPreviousVersion error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Restaure la version précédente d'un jeu et son manifeste
+   * @param {number} gameId - Identifiant du jeu
+   * @returns {Promise<GameManifestLocal>} - Manifeste de la version restaurée
+   */
+  public static async rollbackGame(gameId: number): Promise<GameManifestLocal> {
+    try {
+      return await invoke('rollback_game', { gameId })
+    } catch (error) {
+      console.error('rollbackGame error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère le canal de version installé d'un jeu
+   * @param {number} gameId - Identifiant du jeu
+   * @returns {Promise<ReleaseChannel>} - Canal installé
+   */
+  public static async getReleaseChannel(gameId: number): Promise<ReleaseChannel> {
+    try {
+      return await invoke('get_release_channel', { gameId })
+    } catch (error) {
+      console.error('getReleaseChannel error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Construit le dossier du bucket d'une version d'un canal (contient le manifest.json et les fichiers)
+   * @param {string} pathFilename - Chemin du jeu dans le bucket
+   * @param {ReleaseChannel} channel - Canal de version
+   * @param {string} gameVersion - Version du jeu (ex: v1.0.0)
+   * @param {string} osArchitecture - Architecture du système
+   * @returns {Promise<string>} - Dossier de la version dans le bucket
+   */
+  public static async getReleaseChannelPath(
+    pathFilename: string,
+    channel: ReleaseChannel,
+    gameVersion: string,
+    osArchitecture: string,
+  ): Promise<string> {
+    try {
+      return await invoke('get_release_channel_path', { pathFilename, channel, gameVersion, osArchitecture })
+    } catch (error) {
+      console.error('getReleaseChannelPath error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Calcule les fichiers à télécharger et à supprimer pour passer à la version cible d'un canal
+   * @param {number} gameId - Identifiant du jeu
+   * @param {GameManifestRemote} gameManifestRemote - Fichier manifest.json de la version cible
+   * @returns {Promise<ChannelDiff>} - Différence entre la version installée et la version cible
+   */
+  public static async computeChannelDiff(gameId: number, gameManifestRemote: GameManifestRemote): Promise<ChannelDiff> {
+    try {
+      return await invoke('compute_channel_diff', { gameId, gameManifestRemote })
+    } catch (error) {
+      console.error('computeChannelDiff error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Liste les composants optionnels d'un jeu avec leur taille et s'ils sont installés
+   * @param {number} gameId - Identifiant du jeu
+   * @param {GameManifestRemote} gameManifestRemote - Fichier manifest.json du jeu côté serveur
+   * @returns {Promise<GameComponentInfo[]>} - Composants du jeu
+   */
+  public static async listGameComponents(
+    gameId: number,
+    gameManifestRemote: GameManifestRemote,
+  ): Promise<GameComponentInfo[]> {
+    try {
+      return await invoke('list_game_components', { gameId, gameManifestRemote })
+    } catch (error) {
+      console.error('listGameComponents error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Calcule les fichiers à télécharger pour ajouter un composant à un jeu installé
+   * (le téléchargement se fait ensuite avec downloadGame et les composants retournés)
+   * @param {number} gameId - Identifiant du jeu
+   * @param {string} componentId - Identifiant du composant
+   * @param {GameManifestRemote} gameManifestRemote - Fichier manifest.json du jeu côté serveur
+   * @returns {Promise<ComponentChange>} - Composants et fichiers à télécharger
+   */
+  public static async addGameComponent(
+    gameId: number,
+    componentId: string,
+    gameManifestRemote: GameManifestRemote,
+  ): Promise<ComponentChange> {
+    try {
+      return await invoke('add_game_component', { gameId, componentId, gameManifestRemote })
+    } catch (error) {
+      console.error('addGameComponent error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Supprime les fichiers d'un composant installé
+   * @param {number} gameId - Identifiant du jeu
+   * @param {string} componentId - Identifiant du composant
+   * @param {GameManifestRemote} gameManifestRemote - Fichier manifest.json du jeu côté serveur
+   * @returns {Promise<GameManifestLocal>} - Manifeste local mis à jour
+   */
+  public static async removeGameComponent(
+    gameId: number,
+    componentId: string,
+    gameManifestRemote: GameManifestRemote,
+  ): Promise<GameManifestLocal> {
+    try {
+      return await invoke('remove_game_component', { gameId, componentId, gameManifestRemote })
+    } catch (error) {
+      console.error('removeGameComponent error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Récupère la taille de chaque langue d'un jeu avant l'installation
+   * @param {GameManifestRemote} gameManifestRemote - Fichier manifest.json du jeu côté serveur
+   * @param {string[]} components - Composants à installer, ceux par défaut si absent
+   * @returns {Promise<LanguageSize[]>} - Taille de chaque langue
+   */
+  public static async getLanguageSizes(
+    gameManifestRemote: GameManifestRemote,
+    components?: string[],
+  ): Promise<LanguageSize[]> {
+    try {
+      return await invoke('get_language_sizes', { gameManifestRemote, components })
+    } catch (error) {
+      console.error('getLanguageSizes error:', error)
+      throw error
+    }
+  }
+
+  /**
+   * Change les langues d'un jeu installé : les fichiers des langues retirées sont supprimés
+   * (le téléchargement des nouvelles langues se fait ensuite avec downloadGame et les langues retournées)
+   * @param {number} gameId - Identifiant du jeu
+   * @param {string[]} languages - Langues à installer
+   * @param {GameManifestRemote} gameManifestRemote - Fichier manifest.json du jeu côté serveur
+   * @returns {Promise<LanguageChange>} - Langues et fichiers à télécharger
+   */
+  public static async setGameLanguages(
+    gameId: number,
+    languages: string[],
+    gameManifestRemote: GameManifestRemote,
+  ): Promise<LanguageChange> {
+    try {
+      return await invoke('set_game_languages', { gameId, languages, gameManifestRemote })
+    } catch (error) {
+      console.error('setGameLanguages error:', error)
       throw error
     }
   }
@@ -5,27 +5,27 @@ use tauri::{
     Window,
     Manager,
     Emitter,
-    menu::{MenuBuilder, MenuItemBuilder},
+    menu::{Menu, MenuBuilder, MenuItemBuilder},
     tray::{TrayIconBuilder, TrayIconEvent, MouseButton},
     image::Image,
 };
 use tauri_plugin_log::{Target, TargetKind};
 use tauri_plugin_autostart::MacosLauncher;
+use tauri_plugin_deep_link::DeepLinkExt;
 use std::env;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::{BufReader, Read};
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use core::time::Duration;
 use serde_json::json;
 use dirs;
-use sysinfo::{ Disks, System };
+use sysinfo::Disks;
 use zip::ZipArchive;
 use futures::StreamExt;
 use sha2::{Digest, Sha256};
 use tokio::sync::mpsc;
 use std::thread;
-use std::fs::remove_dir_all;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -76,22 +76,88 @@ fn get_system_os_info_current() -> SystemOSInfo {
     SystemOSInfo { os, architecture }
 }
 
+// Normalise les noms d'architecture (ceux de tauri-plugin-os et ceux de get_system_os_info_current)
+fn normalize_architecture(architecture: &str) -> String {
+    match architecture.to_lowercase().as_str() {
+        "x86_64" | "amd64" | "x64" => "x64".to_string(),
+        "aarch64" | "arm64" => "arm64".to_string(),
+        "i386" | "i686" | "x86" => "x86".to_string(),
+        other => other.to_string(),
+    }
+}
+
+// Vérifie qu'un chemin du manifest reste bien à l'intérieur du dossier du jeu
+fn is_safe_relative_path(relative_path: &str) -> bool {
+    let path = Path::new(relative_path);
+    !relative_path.is_empty()
+        && path.components().all(|component| matches!(component, std::path::Component::Normal(_) | std::path::Component::CurDir))
+}
+
 #[tauri::command]
 async fn check_disk_space(path: String) -> Result<u64, String> {
-    let mut system = System::new_all();
-    system.refresh_all();  // Refresh the system to get the latest information
-
     // Utilisation de Disks pour accéder aux informations de disque
     let disks = Disks::new_with_refreshed_list();
-    for disk in disks.iter() {
-        if let Some(mount_point) = disk.mount_point().to_str() {
-            if mount_point == path {
-                return Ok(disk.available_space());
-            }
-        }
+    find_disk_for_path(&disks, Path::new(&path))
+        .map(|disk| disk.available_space())
+        .ok_or_else(|| format!("Aucun disque trouvé pour le chemin fourni: {}", path))
+}
+
+// Disque qui contient un chemin quelconque (dossier d'installation pas encore créé, lien symbolique...)
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct DiskSpaceInfo {
+    mountPoint: String,
+    fileSystem: String,
+    totalSpace: u64,
+    availableSpace: u64,
+}
+
+#[tauri::command]
+fn get_disk_space(path: String) -> Result<DiskSpaceInfo, String> {
+    let disks = Disks::new_with_refreshed_list();
+    let disk = find_disk_for_path(&disks, Path::new(&path))
+        .ok_or_else(|| format!("Aucun disque trouvé pour le chemin fourni: {}", path))?;
+
+    Ok(DiskSpaceInfo {
+        mountPoint: disk.mount_point().to_string_lossy().into_owned(),
+        fileSystem: disk.file_system().to_string_lossy().into_owned(),
+        totalSpace: disk.total_space(),
+        availableSpace: disk.available_space(),
+    })
+}
+
+// Disque qui contient path : point de montage le plus long qui préfixe le chemin existant le plus proche
+fn find_disk_for_path<'a>(disks: &'a Disks, path: &Path) -> Option<&'a sysinfo::Disk> {
+    let path = resolve_existing_path(path)?;
+    disks.iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+}
+
+// Premier parent existant du chemin (le dossier du jeu n'existe pas encore), avec les liens symboliques résolus
+fn resolve_existing_path(path: &Path) -> Option<PathBuf> {
+    let absolute_path = if path.is_absolute() { path.to_path_buf() } else { env::current_dir().ok()?.join(path) };
+    absolute_path.ancestors()
+        .find_map(|ancestor| ancestor.canonicalize().ok())
+        .map(strip_verbatim_prefix)
+}
+
+// canonicalize renvoie "\\?\C:\..." sous Windows alors que les points de montage sont "C:\"
+#[cfg(target_os = "windows")]
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    let path_str = path.to_string_lossy().into_owned();
+    if let Some(unc_path) = path_str.strip_prefix(r"\\?\UNC\") {
+        PathBuf::from(format!(r"\\{}", unc_path))
+    } else if let Some(local_path) = path_str.strip_prefix(r"\\?\") {
+        PathBuf::from(local_path)
+    } else {
+        path
     }
+}
 
-    Err(format!("Aucun disque trouvé pour le chemin fourni: {}", path))
+#[cfg(not(target_os = "windows"))]
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    path
 }
 
 // getLauncherPathDirectory
@@ -107,6 +173,82 @@ fn get_launcher_path_directory() -> Result<String, String> {
     parent_dir.to_str().map(String::from).ok_or("Error converting path to string".to_string())
 }
 
+// Chemin d'un fichier de données du launcher (ex: Windows = %APPDATA%/com.crzgames.launcher/..)
+fn get_launcher_data_file(app: &tauri::AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| format!("Error obtaining app data directory: {}", e))?;
+    fs::create_dir_all(&app_data_dir).map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    Ok(app_data_dir.join(file_name))
+}
+
+// Lit un fichier JSON de données du launcher, valeur par défaut s'il n'existe pas encore
+fn load_launcher_data<T: serde::de::DeserializeOwned + Default>(app: &tauri::AppHandle, file_name: &str) -> Result<T, String> {
+    let data_path = get_launcher_data_file(app, file_name)?;
+    if !data_path.exists() {
+        return Ok(T::default());
+    }
+
+    let content = fs::read_to_string(&data_path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", file_name, e))
+}
+
+fn save_launcher_data<T: serde::Serialize>(app: &tauri::AppHandle, file_name: &str, data: &T) -> Result<(), String> {
+    let data_path = get_launcher_data_file(app, file_name)?;
+    let content = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+    fs::write(&data_path, content).map_err(|e| format!("Failed to save {}: {}", file_name, e))
+}
+
+// Comportement de la fenêtre du launcher pendant qu'un jeu est lancé
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum LaunchWindowBehavior {
+    #[default]
+    None,
+    Minimize,
+    Hide,
+}
+
+// Paramètres du launcher (launcherSettings.json)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct LauncherSettings {
+    #[serde(default)]
+    launchWindowBehavior: LaunchWindowBehavior,
+    // Garder les fichiers remplacés par la dernière mise à jour pour pouvoir revenir en arrière
+    #[serde(default)]
+    keepPreviousVersion: bool,
+    // Au-delà de cette taille (en octets), la version précédente n'est pas gardée
+    #[serde(default = "default_previous_version_max_size")]
+    previousVersionMaxSize: u64,
+}
+
+fn default_previous_version_max_size() -> u64 {
+    5 * 1024 * 1024 * 1024
+}
+
+impl Default for LauncherSettings {
+    fn default() -> Self {
+        LauncherSettings {
+            launchWindowBehavior: LaunchWindowBehavior::default(),
+            keepPreviousVersion: false,
+            previousVersionMaxSize: default_previous_version_max_size(),
+        }
+    }
+}
+
+fn load_launcher_settings(app: &tauri::AppHandle) -> Result<LauncherSettings, String> {
+    load_launcher_data(app, "launcherSettings.json")
+}
+
+#[tauri::command]
+fn get_launcher_settings(app: tauri::AppHandle) -> Result<LauncherSettings, String> {
+    load_launcher_settings(&app)
+}
+
+#[tauri::command]
+fn set_launcher_settings(app: tauri::AppHandle, settings: LauncherSettings) -> Result<(), String> {
+    save_launcher_data(&app, "launcherSettings.json", &settings)
+}
+
 fn remove_duplicates(manifest: &mut GameManifestLocal) {
     let mut seen = std::collections::HashSet::new();
     manifest.files.retain(|file| seen.insert(file.name.clone()));
@@ -127,37 +269,187 @@ fn check_missing_files(file_location_download: String, local_manifest: GameManif
     Ok(missing_files)
 }
 
+// Résultat de la vérification avant le lancement d'un jeu
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "status", rename_all = "camelCase", rename_all_fields = "camelCase")]
+enum PreLaunchStatus {
+    Ok,
+    UpdateRequired {
+        local_version: String,
+        remote_version: String,
+    },
+    RepairRequired {
+        missing_files: Vec<FileDetails>,
+        size_mismatch_files: Vec<FileDetails>,
+    },
+    AlreadyRunning {
+        game_id: u64,
+    },
+}
+
+// Vérification rapide (fichiers manquants, tailles différentes) et comparaison de la version
+// locale avec celle du serveur, sans calculer les hash
+fn check_game_before_launch(game_directory: &Path, local_manifest: &GameManifestLocal, remote_version: Option<&str>, verify_files: bool) -> PreLaunchStatus {
+    if let Some(remote_version) = remote_version {
+        if local_manifest.version != remote_version {
+            return PreLaunchStatus::UpdateRequired {
+                local_version: local_manifest.version.clone(),
+                remote_version: remote_version.to_string(),
+            };
+        }
+    }
+
+    if verify_files {
+        let mut missing_files = Vec::new();
+        let mut size_mismatch_files = Vec::new();
+        for file in &local_manifest.files {
+            match fs::metadata(game_directory.join(&file.name)) {
+                // Un fichier à conserver peut avoir été modifié par le joueur
+                Ok(_) if is_preserved_path(&file.name, &local_manifest.preservedPaths) => {}
+                Ok(metadata) if metadata.is_file() && metadata.len() != file.size => size_mismatch_files.push(file.clone()),
+                Ok(_) => {}
+                Err(_) => missing_files.push(file.clone()),
+            }
+        }
+
+        if !missing_files.is_empty() || !size_mismatch_files.is_empty() {
+            return PreLaunchStatus::RepairRequired { missing_files, size_mismatch_files };
+        }
+    }
+
+    PreLaunchStatus::Ok
+}
+
+#[tauri::command]
+fn verify_game_before_launch(file_location_download: String, remote_version: Option<String>) -> Result<PreLaunchStatus, String> {
+    let local_manifest = read_local_manifest(&file_location_download)?
+        .ok_or_else(|| format!("manifest_local.json not found in {}", file_location_download))?;
+
+    Ok(check_game_before_launch(Path::new(&file_location_download), &local_manifest, remote_version.as_deref(), true))
+}
+
+// Nettoyage du dossier d'un jeu installé (vérification / réparation) en dehors d'un téléchargement :
+// refusé pendant un téléchargement du jeu, et si le téléchargement est en pause ses fichiers partiels
+// sont gardés pour la reprise
+#[tauri::command]
+fn clean_up_game(app: tauri::AppHandle, game_id: u64) -> Result<(), String> {
+    if is_download_active(game_id) {
+        return Err(format!("Game {} is being downloaded, wait for the download to finish before cleaning it up", game_id));
+    }
+    let path_install_location = installed_game_directory(&app, game_id)?;
+    let game_manifest = read_local_manifest(&path_install_location)?
+        .ok_or_else(|| format!("manifest_local.json not found in {}", path_install_location))?;
+    clean_up_directory(Path::new(&path_install_location), &game_manifest)
+}
+
 fn clean_up_directory(game_directory: &Path, game_manifest: &GameManifestLocal) -> Result<(), String> {
-    // Vérifier et supprimer les fichiers et dossiers indésirables
+    // Vérifier et supprimer les fichiers et dossiers indésirables, à toutes les profondeurs
     let manifest_files: HashSet<PathBuf> = game_manifest.files.iter().map(|f| game_directory.join(&f.name)).collect();
-    let mut to_delete = Vec::new();
 
+    // Les fichiers .tmp d'un téléchargement en pause seront repris au prochain lancement (nettoyage
+    // lancé par clean_up_game, un téléchargement terminé n'est jamais en pause)
+    let keep_partial_downloads = is_download_paused(game_manifest.gameId);
+
+    clean_up_entries(game_directory, game_directory, &manifest_files, &game_manifest.preservedPaths, keep_partial_downloads)
+}
+
+// Supprime les fichiers de dir absents du manifeste (sauf les données du joueur) et les dossiers devenus vides,
+// sans suivre les liens symboliques
+fn clean_up_entries(
+    game_directory: &Path,
+    dir: &Path,
+    manifest_files: &HashSet<PathBuf>,
+    preserved_paths: &[String],
+    keep_partial_downloads: bool,
+) -> Result<(), String> {
     // Chemin complet du fichier manifest_local.json
     let manifest_file_path = game_directory.join("manifest_local.json");
 
-    // Parcourir les fichiers et dossiers du répertoire du jeu
-    for entry in fs::read_dir(&game_directory).map_err(|e| e.to_string())? {
+    for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read directory: {}: {}", dir.display(), e))? {
         let entry = entry.map_err(|e| e.to_string())?;
         let path = entry.path();
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
 
-        // Si le chemin n'est pas dans les fichiers du manifeste, l'ajouter à la liste des suppressions
-        if path != manifest_file_path && !manifest_files.contains(&path) && !manifest_files.iter().any(|f| f.starts_with(&path)) {
-            to_delete.push(path);
+        if path == manifest_file_path || is_preserved_path(&relative_display_path(game_directory, &path), preserved_paths) {
+            continue;
         }
-    }
 
-    // Supprimer les fichiers et dossiers indésirables
-    for path in to_delete {
-        if path.is_dir() {
-            fs::remove_dir_all(&path).map_err(|e| format!("Failed to remove directory: {}: {}", path.display(), e))?;
-        } else {
-            fs::remove_file(&path).map_err(|e| format!("Failed to remove file: {}: {}", path.display(), e))?;
+        if path == game_directory.join(PREVIOUS_VERSION_DIR_NAME) {
+            continue;
+        }
+
+        // Mise à jour préparée abandonnée, gardée si le téléchargement est en pause
+        if path == game_directory.join(STAGING_DIR_NAME) {
+            if !keep_partial_downloads {
+                fs::remove_dir_all(&path).map_err(|e| format!("Failed to remove directory: {}: {}", path.display(), e))?;
+            }
+            continue;
+        }
+
+        if file_type.is_dir() {
+            clean_up_entries(game_directory, &path, manifest_files, preserved_paths, keep_partial_downloads)?;
+            if is_empty_directory(&path) {
+                fs::remove_dir(&path).map_err(|e| format!("Failed to remove directory: {}: {}", path.display(), e))?;
+            }
+            continue;
         }
+
+        // Fichier du manifeste, ou lien symbolique vers un dossier qui contient des fichiers du manifeste
+        if manifest_files.iter().any(|f| f.starts_with(&path)) {
+            continue;
+        }
+        if keep_partial_downloads && path.extension().is_some_and(|ext| ext == "tmp") {
+            continue;
+        }
+
+        fs::remove_file(&path).map_err(|e| format!("Failed to remove file: {}: {}", path.display(), e))?;
     }
 
     Ok(())
 }
 
+// Chemins à conserver déclarés par le manifest : "*" et "?" dans un nom, "**" pour plusieurs dossiers.
+// Un motif sans "/" s'applique à chaque élément du chemin (ex: "*.ini", "screenshots"), sinon au chemin
+// relatif au dossier du jeu ou à l'un de ses dossiers parents (ex: "saves/**", "config/user.cfg")
+fn is_preserved_path(relative_path: &str, preserved_paths: &[String]) -> bool {
+    let relative_segments = path_segments(relative_path);
+    preserved_paths.iter().any(|preserved_path| {
+        let pattern_segments = path_segments(preserved_path);
+        match pattern_segments.as_slice() {
+            [] => false,
+            [pattern] if *pattern != "**" => relative_segments.iter().any(|segment| glob_match_name(pattern, segment)),
+            _ => (1..=relative_segments.len()).any(|len| glob_match_segments(&pattern_segments, &relative_segments[..len])),
+        }
+    })
+}
+
+fn path_segments(path: &str) -> Vec<&str> {
+    path.split(['/', '\\']).filter(|segment| !segment.is_empty() && *segment != ".").collect()
+}
+
+fn glob_match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skipped| glob_match_segments(rest, &path[skipped..])),
+        Some((segment, rest)) => !path.is_empty() && glob_match_name(segment, path[0]) && glob_match_segments(rest, &path[1..]),
+    }
+}
+
+fn glob_match_name(pattern: &str, name: &str) -> bool {
+    fn glob_match_chars(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skipped| glob_match_chars(rest, &name[skipped..])),
+            Some(('?', rest)) => !name.is_empty() && glob_match_chars(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && glob_match_chars(rest, &name[1..]),
+        }
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    glob_match_chars(&pattern, &name)
+}
+
 type DownloadState = Arc<Mutex<HashMap<u64, (Arc<AtomicBool>, Arc<AtomicBool>)>>>;
 
 lazy_static! {
@@ -169,6 +461,159 @@ fn get_or_create_download_state(game_id: u64) -> (Arc<AtomicBool>, Arc<AtomicBoo
     states.entry(game_id).or_insert_with(|| (Arc::new(AtomicBool::new(false)), Arc::new(AtomicBool::new(false)))).clone()
 }
 
+lazy_static! {
+    static ref ACTIVE_DOWNLOADS: Mutex<HashSet<u64>> = Mutex::new(HashSet::new());
+}
+
+// Marque le téléchargement d'un jeu comme en cours jusqu'à la fin de download_and_update_game,
+// y compris en cas d'erreur, d'annulation ou de pause
+struct ActiveDownloadGuard(u64);
+
+impl ActiveDownloadGuard {
+    fn new(game_id: u64) -> Self {
+        ACTIVE_DOWNLOADS.lock().unwrap().insert(game_id);
+        ActiveDownloadGuard(game_id)
+    }
+}
+
+impl Drop for ActiveDownloadGuard {
+    fn drop(&mut self) {
+        ACTIVE_DOWNLOADS.lock().unwrap().remove(&self.0);
+    }
+}
+
+fn is_download_active(game_id: u64) -> bool {
+    ACTIVE_DOWNLOADS.lock().unwrap().contains(&game_id)
+}
+
+// Session d'un jeu lancé par le launcher, le processus est None tant que le jeu démarre
+#[derive(Clone)]
+struct RunningGame {
+    game_title: String,
+    child: Arc<Mutex<Option<std::process::Child>>>,
+    stop_requested: Arc<AtomicBool>,
+}
+
+lazy_static! {
+    static ref RUNNING_GAMES: Mutex<HashMap<u64, Vec<RunningGame>>> = Mutex::new(HashMap::new());
+}
+
+// Réserve une session pour le jeu, None si le jeu est déjà lancé et que le manifest
+// n'autorise pas plusieurs instances
+fn register_game_session(game_id: u64, game_title: &str, allow_multiple_instances: bool) -> Option<RunningGame> {
+    let mut running_games = RUNNING_GAMES.lock().unwrap();
+    let sessions = running_games.entry(game_id).or_default();
+    if !sessions.is_empty() && !allow_multiple_instances {
+        return None;
+    }
+
+    let session = RunningGame {
+        game_title: game_title.to_string(),
+        child: Arc::new(Mutex::new(None)),
+        stop_requested: Arc::new(AtomicBool::new(false)),
+    };
+    sessions.push(session.clone());
+    Some(session)
+}
+
+fn unregister_game_session(game_id: u64, session: &RunningGame) {
+    let mut running_games = RUNNING_GAMES.lock().unwrap();
+    if let Some(sessions) = running_games.get_mut(&game_id) {
+        sessions.retain(|running_game| !Arc::ptr_eq(&running_game.child, &session.child));
+        if sessions.is_empty() {
+            running_games.remove(&game_id);
+        }
+    }
+}
+
+#[tauri::command]
+fn stop_game(app: tauri::AppHandle, game_id: u64) -> Result<(), String> {
+    let sessions = RUNNING_GAMES.lock().unwrap().get(&game_id).cloned().unwrap_or_default();
+    if sessions.is_empty() {
+        return Err(format!("Game {} is not running", game_id));
+    }
+
+    // Arrêter toutes les instances du jeu
+    // La demande d'arrêt est enregistrée sous le verrou du processus : un jeu en cours de
+    // démarrage la voit au moment où il est rattaché à la session
+    for session in sessions {
+        let mut child = session.child.lock().unwrap();
+        session.stop_requested.store(true, Ordering::Relaxed);
+        if let Some(child) = child.as_mut() {
+            child.kill().map_err(|e| format!("Failed to stop game: {}", e))?;
+        }
+    }
+    refresh_tray(&app);
+    Ok(())
+}
+
+// Menu de la barre d'état, avec une entrée par jeu en cours d'exécution
+fn build_tray_menu(app: &tauri::AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let mut running_games: Vec<(u64, String)> = RUNNING_GAMES.lock().unwrap()
+        .iter()
+        .filter_map(|(game_id, sessions)| sessions.first().map(|session| (*game_id, session.game_title.clone())))
+        .collect();
+    running_games.sort();
+
+    let mut menu_builder = MenuBuilder::new(app);
+    for (game_id, game_title) in &running_games {
+        let playing = MenuItemBuilder::new(format!("Playing {}", game_title))
+            .id(format!("playing-game-{}", game_id))
+            .enabled(false)
+            .build(app)?;
+        let stop = MenuItemBuilder::new(format!("Stop {}", game_title))
+            .id(format!("stop-game-{}", game_id))
+            .build(app)?;
+        menu_builder = menu_builder.items(&[&playing, &stop]);
+    }
+    if !running_games.is_empty() {
+        menu_builder = menu_builder.separator();
+    }
+
+    let show = MenuItemBuilder::new("Open CrzGames").id("show").build(app)?;
+    let hide = MenuItemBuilder::new("Hide CrzGames").id("hide").build(app)?;
+    let quit = MenuItemBuilder::new("Quit CrzGames").id("quit").build(app)?;
+    menu_builder.items(&[&show, &hide, &quit]).build()
+}
+
+// Met à jour le menu et l'info-bulle de la barre d'état selon les jeux en cours d'exécution
+fn refresh_tray(app: &tauri::AppHandle) {
+    let Some(tray) = app.tray_by_id("main") else {
+        return;
+    };
+
+    let mut game_titles: Vec<String> = RUNNING_GAMES.lock().unwrap()
+        .values()
+        .filter_map(|sessions| sessions.first().map(|session| session.game_title.clone()))
+        .collect();
+    game_titles.sort();
+    let tooltip = if game_titles.is_empty() {
+        "CrzGames".to_string()
+    } else {
+        format!("CrzGames - Playing {}", game_titles.join(", "))
+    };
+
+    if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+        eprintln!("Failed to update tray tooltip: {}", e);
+    }
+    match build_tray_menu(app) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                eprintln!("Failed to update tray menu: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Failed to build tray menu: {}", e),
+    }
+}
+
+fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
 #[tauri::command]
 fn cancel_download(game_id: u64) {
     let (cancel, _) = get_or_create_download_state(game_id);
@@ -188,10 +633,16 @@ fn resume_download(game_id: u64) {
     pause.store(false, Ordering::Relaxed);
 }
 
+fn is_download_paused(game_id: u64) -> bool {
+    let states = DOWNLOAD_STATES.lock().unwrap();
+    states.get(&game_id).is_some_and(|(_, pause)| pause.load(Ordering::Relaxed))
+}
+
 fn remove_obsolete_files(
     game_directory: &Path,
     local_manifest: &mut GameManifestLocal,
-    remote_manifest: &GameManifestRemote
+    remote_manifest: &GameManifestRemote,
+    previous_files_directory: Option<&Path>,
 ) -> Result<(), String> {
     // Crée un ensemble de tuples (name, size, hash) pour les fichiers du manifeste distant
     let remote_files: HashSet<_> = remote_manifest.files.iter().map(|file| (&file.name, file.size, &file.hash)).collect();
@@ -205,10 +656,21 @@ fn remove_obsolete_files(
         let is_still_valid = remote_files.contains(&local_file_key);
 
         // Si le fichier local n'est plus présent dans le manifeste distant, il est considéré comme obsolète
-        if !is_still_valid {
+        // (un fichier à conserver reste sur le disque et suivi par le manifeste, il ne sera pas remplacé)
+        if !is_still_valid && is_preserved_path(&local_file.name, &remote_manifest.preservedPaths) {
+            println!("Keeping preserved file: {}", local_file.name);
+            return true;
+        } else if !is_still_valid {
             let file_path = game_directory.join(&local_file.name);
 
-            // Supprime le fichier obsolète du disque
+            // Garde le fichier remplacé avec la version précédente, sinon le supprime du disque
+            let kept_file = previous_files_directory
+                .filter(|_| file_path.is_file())
+                .map(|previous_files_directory| move_file(&file_path, &previous_files_directory.join(&local_file.name)));
+            if let Some(Err(e)) = &kept_file {
+                eprintln!("Failed to keep previous version of {}: {}", local_file.name, e);
+            }
+
             if file_path.exists() {
                 if file_path.is_dir() {
                     if let Err(e) = fs::remove_dir_all(&file_path) {
@@ -229,6 +691,165 @@ fn remove_obsolete_files(
     Ok(())
 }
 
+// Dossier de préparation d'une mise à jour, dans le dossier du jeu pour que les renommages restent
+// sur le même disque : files/ (nouveaux fichiers), manifest_local.json (version en préparation),
+// journal.json (bascule en cours) et previous/ (fichiers remplacés)
+const STAGING_DIR_NAME: &str = ".crzgames-staging";
+
+// Bascule vers la nouvelle version, écrite quand tous les fichiers préparés sont vérifiés
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct StagingJournal {
+    manifest: GameManifestLocal,
+    stagedFiles: Vec<String>,
+    obsoleteFiles: Vec<String>,
+    // Manifeste de la version remplacée, si elle doit être gardée
+    #[serde(default)]
+    previousManifest: Option<GameManifestLocal>,
+}
+
+// Version précédente gardée après une mise à jour : files/ (fichiers remplacés ou supprimés par
+// la mise à jour) et manifest_local.json (manifeste de cette version)
+const PREVIOUS_VERSION_DIR_NAME: &str = ".crzgames-previous";
+
+// Remplace la version précédente gardée par celle décrite par previous_manifest (aucune si None)
+fn reset_previous_version(game_directory: &Path, previous_manifest: Option<&GameManifestLocal>) -> Result<(), String> {
+    let previous_directory = game_directory.join(PREVIOUS_VERSION_DIR_NAME);
+    if previous_directory.exists() {
+        fs::remove_dir_all(&previous_directory)
+            .map_err(|e| format!("Failed to remove previous version: {}: {}", previous_directory.display(), e))?;
+    }
+
+    if let Some(previous_manifest) = previous_manifest {
+        fs::create_dir_all(previous_directory.join("files")).map_err(|e| format!("Failed to create previous version directory: {}", e))?;
+        save_manifest(&previous_directory.to_string_lossy(), previous_manifest)?;
+    }
+    Ok(())
+}
+
+fn directory_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries.filter_map(Result::ok)
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => directory_size(&entry.path()),
+            _ => entry.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+        })
+        .sum()
+}
+
+// Supprime la version précédente gardée si elle dépasse la taille maximale
+fn enforce_previous_version_max_size(game_directory: &Path, max_size: u64) -> Result<(), String> {
+    let previous_directory = game_directory.join(PREVIOUS_VERSION_DIR_NAME);
+    let previous_size = directory_size(&previous_directory.join("files"));
+    if previous_directory.exists() && previous_size > max_size {
+        println!("Previous version is too large ({} > {} bytes), it is not kept", previous_size, max_size);
+        reset_previous_version(game_directory, None)?;
+    }
+    Ok(())
+}
+
+// Retire du manifeste les fichiers absents du manifeste distant, sans toucher au disque
+// (les fichiers à conserver restent suivis)
+fn retain_current_files(local_manifest: &mut GameManifestLocal, remote_manifest: &GameManifestRemote) {
+    let remote_files: HashSet<_> = remote_manifest.files.iter().map(|file| (&file.name, file.size, &file.hash)).collect();
+    local_manifest.files.retain(|local_file| {
+        remote_files.contains(&(&local_file.name, local_file.size, &local_file.hash))
+            || is_preserved_path(&local_file.name, &remote_manifest.preservedPaths)
+    });
+}
+
+// Donnée du joueur livrée avec le jeu (ex: "config.ini" avec "*.ini") : la copie du disque n'est jamais remplacée
+fn is_kept_preserved_file(game_directory: &Path, file_name: &str, preserved_paths: &[String]) -> bool {
+    is_preserved_path(file_name, preserved_paths) && game_directory.join(file_name).is_file()
+}
+
+fn move_file(source: &Path, destination: &Path) -> Result<(), String> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}: {}", parent.display(), e))?;
+    }
+    fs::rename(source, destination)
+        .map_err(|e| format!("Failed to move {} to {}: {}", source.display(), destination.display(), e))
+}
+
+// Remplace les fichiers du jeu par les fichiers préparés. Chaque étape peut être rejouée :
+// après une interruption, le journal permet de terminer la bascule
+fn switch_staged_update(game_directory: &Path, journal: &StagingJournal) -> Result<(), String> {
+    let staging_directory = game_directory.join(STAGING_DIR_NAME);
+    let staged_files_directory = staging_directory.join("files");
+    let previous_directory = staging_directory.join("previous");
+
+    // Mettre de côté les fichiers remplacés ou obsolètes de la version actuelle. Un fichier préparé déjà
+    // déplacé est la nouvelle version : rejouer cette étape après une interruption ne doit pas le retirer
+    let replaced_files = journal.stagedFiles.iter().filter(|name| staged_files_directory.join(name).exists());
+    for name in replaced_files.chain(&journal.obsoleteFiles) {
+        let live_path = game_directory.join(name);
+        let previous_path = previous_directory.join(name);
+        if live_path.is_file() && !previous_path.exists() {
+            move_file(&live_path, &previous_path)?;
+        }
+    }
+
+    for name in &journal.stagedFiles {
+        let staged_path = staged_files_directory.join(name);
+        if staged_path.exists() {
+            move_file(&staged_path, &game_directory.join(name))?;
+        }
+    }
+
+    save_manifest(&game_directory.to_string_lossy(), &journal.manifest)?;
+
+    // Les fichiers mis de côté deviennent la version précédente, ou sont supprimés avec le dossier de préparation.
+    // Une version précédente avec un manifeste est celle d'avant : elle est toujours retirée. Sans manifeste,
+    // ce sont les fichiers déjà déplacés par une bascule interrompue
+    let previous_version_directory = game_directory.join(PREVIOUS_VERSION_DIR_NAME);
+    let previous_version_files_directory = previous_version_directory.join("files");
+    if journal.previousManifest.is_none() || previous_directory.exists() || previous_version_directory.join("manifest_local.json").exists() {
+        reset_previous_version(game_directory, None)?;
+    }
+    if journal.previousManifest.is_some() {
+        fs::create_dir_all(&previous_version_directory).map_err(|e| format!("Failed to create previous version directory: {}", e))?;
+        if previous_directory.exists() {
+            fs::rename(&previous_directory, &previous_version_files_directory).map_err(|e| format!("Failed to keep previous version: {}", e))?;
+        } else {
+            fs::create_dir_all(&previous_version_files_directory).map_err(|e| format!("Failed to create previous version directory: {}", e))?;
+        }
+    }
+
+    // Le journal est terminé avant que la version précédente ne devienne utilisable
+    fs::remove_file(staging_directory.join("journal.json")).map_err(|e| format!("Failed to remove staging journal: {}", e))?;
+    fs::remove_dir_all(&staging_directory)
+        .map_err(|e| format!("Failed to remove staging directory: {}: {}", staging_directory.display(), e))?;
+
+    if let Some(previous_manifest) = &journal.previousManifest {
+        save_manifest(&previous_version_directory.to_string_lossy(), previous_manifest)?;
+    }
+    Ok(())
+}
+
+fn write_staging_journal(game_directory: &Path, journal: &StagingJournal) -> Result<(), String> {
+    let journal_path = game_directory.join(STAGING_DIR_NAME).join("journal.json");
+    let temp_journal_path = journal_path.with_extension("tmp");
+    let content = serde_json::to_string_pretty(journal).map_err(|e| e.to_string())?;
+    fs::write(&temp_journal_path, content).map_err(|e| format!("Failed to write staging journal: {}", e))?;
+    fs::rename(&temp_journal_path, &journal_path).map_err(|e| format!("Failed to write staging journal: {}", e))
+}
+
+// Termine une bascule interrompue (fermeture du launcher, coupure...), renvoie true si il y en avait une
+fn recover_staged_update(game_directory: &Path) -> Result<bool, String> {
+    let journal_path = game_directory.join(STAGING_DIR_NAME).join("journal.json");
+    if !journal_path.exists() {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(&journal_path).map_err(|e| format!("Failed to read staging journal: {}", e))?;
+    let journal: StagingJournal = serde_json::from_str(&content).map_err(|e| format!("Failed to parse staging journal: {}", e))?;
+    println!("Resuming interrupted update of {} to {}", game_directory.display(), journal.manifest.version);
+    switch_staged_update(game_directory, &journal)?;
+    Ok(true)
+}
+
 #[tauri::command]
 async fn download_and_update_game(
     webview: Window,
@@ -240,6 +861,12 @@ async fn download_and_update_game(
     file_location_download: String,
     files_to_download: Vec<FileDetails>,
     desktop_shortcut: bool,
+    shortcut_through_launcher: Option<bool>,
+    shortcut_options: Option<ShortcutOptions>,
+    staged_update: Option<bool>,
+    release_channel: Option<ReleaseChannel>,
+    components: Option<Vec<String>>,
+    languages: Option<Vec<String>>,
     game_title: String,
     game_version: String,
     game_binary_size: u64,
@@ -248,7 +875,11 @@ async fn download_and_update_game(
     game_manifest_remote: GameManifestRemote
 ) -> Result<(), String> {
     println!("Starting download for game: {}", game_title);
+    if let Some(release_channel) = &release_channel {
+        release_channel.validate()?;
+    }
 
+    let _active_download = ActiveDownloadGuard::new(game_id);
     let (cancel_flag, pause_flag) = get_or_create_download_state(game_id);
     cancel_flag.store(false, Ordering::Relaxed);
     pause_flag.store(false, Ordering::Relaxed);
@@ -257,37 +888,109 @@ async fn download_and_update_game(
     let game_directory = Path::new(&file_location_download);
     println!("Games directory: {:?}", game_directory);
     fs::create_dir_all(&game_directory).map_err(|e| e.to_string())?;
+    recover_staged_update(game_directory)?;
+
+    // Mise à jour préparée : les fichiers sont téléchargés à part et le jeu reste jouable jusqu'à la bascule
+    let staging_directory = game_directory.join(STAGING_DIR_NAME);
+    let staged_update = staged_update.unwrap_or(false) && read_local_manifest(&file_location_download)?.is_some();
+    let manifest_location = if staged_update {
+        fs::create_dir_all(staging_directory.join("files")).map_err(|e| format!("Failed to create staging directory: {}", e))?;
+        staging_directory.to_string_lossy().into_owned()
+    } else {
+        file_location_download.clone()
+    };
+    let write_directory = if staged_update { staging_directory.join("files") } else { game_directory.to_path_buf() };
+
+    // Version installée avant cette mise à jour, gardée si le paramètre est activé
+    let launcher_settings = load_launcher_settings(webview.app_handle())?;
+    let installed_manifest = read_local_manifest(&file_location_download)?
+        .filter(|installed_manifest| installed_manifest.version != game_version);
+    let previous_manifest = installed_manifest.filter(|_| launcher_settings.keepPreviousVersion);
+    let previous_directory = game_directory.join(PREVIOUS_VERSION_DIR_NAME);
 
     // Charger le manifeste local pour obtenir l'état actuel du téléchargement ou créer un nouveau manifeste
-    let mut game_manifest = load_or_create_manifest(
-        &file_location_download,
-        game_id,
-        game_title.clone(),
-        game_binary_size,
-        game_version.clone(),
-    )?;
-
-    // Supprimer les fichiers obsolètes avant de commencer le téléchargement
-    // Par exemple si la version suivante à supprimer certains fichier / dossier par rapport à la version actuelle
-    remove_obsolete_files(&game_directory, &mut game_manifest, &game_manifest_remote)?;
+    // (celui de la version en préparation pour reprendre une mise à jour préparée)
+    let mut game_manifest = match read_local_manifest(&manifest_location)? {
+        Some(manifest) => manifest,
+        None => load_or_create_manifest(
+            &file_location_download,
+            game_id,
+            game_title.clone(),
+            game_binary_size,
+            game_version.clone(),
+        )?,
+    };
+
+    // Composants installés : ceux demandés, sinon ceux déjà installés (ou proposés par défaut), les fichiers
+    // des autres composants sont ignorés et ceux d'un composant retiré sont traités comme obsolètes
+    let selected_components = match components {
+        Some(components) => game_manifest_remote.resolve_components(Some(&components))?,
+        None => game_manifest_remote.installed_components(&game_manifest)?,
+    };
+
+    // Langues installées : changer de langue télécharge la nouvelle et rend obsolètes les fichiers de l'ancienne
+    let selected_languages = match languages {
+        Some(languages) => game_manifest_remote.resolve_languages(Some(&languages))?,
+        None => game_manifest_remote.installed_languages(&game_manifest)?,
+    };
+
+    let game_manifest_remote = game_manifest_remote
+        .for_components(&selected_components)
+        .for_languages(selected_languages.as_deref());
+    let files_to_download: Vec<FileDetails> = files_to_download.into_iter()
+        .filter(|file| file.is_in_components(&selected_components) && file.is_in_languages(selected_languages.as_deref()))
+        .collect();
+
+    if staged_update {
+        // Les fichiers obsolètes restent sur le disque jusqu'à la bascule
+        retain_current_files(&mut game_manifest, &game_manifest_remote);
+    } else {
+        // Une nouvelle mise à jour remplace la version précédente gardée (reprise : la version a déjà été changée)
+        if game_manifest.version != game_version {
+            reset_previous_version(game_directory, previous_manifest.as_ref())?;
+        }
+        let previous_files_directory = previous_directory.join("files");
+        let previous_files_directory = (launcher_settings.keepPreviousVersion && previous_directory.join("manifest_local.json").exists())
+            .then_some(previous_files_directory.as_path());
+
+        // Supprimer les fichiers obsolètes avant de commencer le téléchargement
+        // Par exemple si la version suivante à supprimer certains fichier / dossier par rapport à la version actuelle
+        remove_obsolete_files(&game_directory, &mut game_manifest, &game_manifest_remote, previous_files_directory)?;
+    }
 
     // Supprimer les doublons dans le manifest_local.json avant de commencer le téléchargement
     remove_duplicates(&mut game_manifest);
 
+    // Changement de canal : le canal demandé est enregistré, sinon celui déjà installé est gardé
+    if let Some(release_channel) = release_channel {
+        game_manifest.channel = release_channel;
+    }
+    resolve_channel_access_key(webview.app_handle(), game_id, &mut game_manifest.channel)?;
+    game_manifest.components = Some(selected_components);
+    game_manifest.languages = selected_languages;
+
     // Mettre à jour la version du jeu et la taille binaire dans le manifeste local
     game_manifest.version = game_version.clone();
     game_manifest.gameBinarySize = game_binary_size;
     game_manifest.gameTitle = game_title.clone();
+    apply_remote_manifest_settings(&mut game_manifest, &game_manifest_remote);
 
     // Sauvegarder le manifeste mis à jour après la suppression des fichiers obsolètes
-    save_manifest(&file_location_download, &game_manifest)?;
+    save_manifest(&manifest_location, &game_manifest)?;
 
     // Calculer le total à télécharger en fonction des fichiers à télécharger
     let total_size_to_download: u64 = files_to_download.iter().map(|file| file.size).sum();
 
     // Si le manifest_local.json existe déjà, récupérer tout les file.size et les sommer
     // Taille totale DEJA téléchargée (pour la reprise du téléchargement si nécessaire)
-    let mut total_downloaded: u64 = calculate_real_total_downloaded(&game_directory, &game_manifest);
+    let mut total_downloaded: u64 = if staged_update {
+        game_manifest.files.iter()
+            .filter(|file| write_directory.join(&file.name).exists() || game_directory.join(&file.name).exists())
+            .map(|file| file.size)
+            .sum()
+    } else {
+        calculate_real_total_downloaded(&game_directory, &game_manifest)
+    };
 
     // Utiliser seulement pour renvoyer le speed du telechargement
     let mut bytes_downloaded = 0;
@@ -309,10 +1012,27 @@ async fn download_and_update_game(
             return Err("Download paused".to_string());
         }
 
+        // Fichier déjà préparé avant une pause
+        if staged_update
+            && write_directory.join(&file.name).exists()
+            && game_manifest.files.iter().any(|staged_file| staged_file.name == file.name && staged_file.hash == file.hash) {
+            continue;
+        }
+
+        // Fichier à conserver déjà présent dans le jeu : ni téléchargé (archive ou fichier) ni préparé
+        if is_kept_preserved_file(game_directory, &file.name, &game_manifest_remote.preservedPaths) {
+            println!("Keeping preserved file: {}", file.name);
+            if !game_manifest.files.iter().any(|tracked_file| tracked_file.name == file.name) {
+                game_manifest.files.push(file.clone());
+            }
+            continue;
+        }
+
         // Construction de l'URL pour la requête GET avec les paramètres
-        let full_path = format!("{}{}/{}/{}", path_filename, game_version, os_architecture, file.name);
+        let full_path = format!("{}{}", game_manifest.channel.build_path(&path_filename, &game_version, &os_architecture), file.name);
         let request_url = format!("{}?bucketName={}&pathFilename={}", api_url, bucket_name, full_path);
         println!("Downloading file, URL API: {}", request_url);
+        let request_url = format!("{}{}", request_url, game_manifest.channel.access_key_query());
 
         // Envoie la requête GET et gère la réponse
         let response = client.get(&request_url)
@@ -332,7 +1052,7 @@ async fn download_and_update_game(
             .to_string();
 
         // Extraire le chemin du fichier à partir de son nom
-        let file_path = game_directory.join(&file.name);
+        let file_path = write_directory.join(&file.name);
         if let Some(parent) = file_path.parent() {
             println!("Creating directory: {}", parent.display());
             fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
@@ -386,7 +1106,7 @@ async fn download_and_update_game(
             // Pour chaque fichier dans l'archive, calculer le hash et mettre à jour le manifest
             for i in 0..zip.len() {
                 let mut zip_file = zip.by_index(i).map_err(|e| format!("Failed to access file in zip: {}", e))?;
-                let out_path = game_directory.join(&file.name);
+                let out_path = write_directory.join(&file.name);
                 println!("Extracted file path: {}", out_path.display());
 
                 // Créez les dossiers nécessaires avant d'extraire le fichier
@@ -399,27 +1119,25 @@ async fn download_and_update_game(
                 zip_file.read_to_end(&mut buffer).map_err(|e| format!("Failed to read zip file: {}", e))?;
                 fs::write(&out_path, &buffer).map_err(|e| format!("Failed to write file: {}", e))?;
 
+                // Droits du manifest, sinon ceux enregistrés dans l'archive
+                apply_unix_mode(&out_path, file.unix_mode().or_else(|| zip_file.unix_mode().map(|mode| mode & 0o777)))?;
+
                 let extracted_hash = calculate_file_hash(&out_path)?;
                 println!("Extracted file hash: {}", extracted_hash);
 
                 // Mettre à jour le manifest local pour chaque fichier extrait
-                let extracted_file_details = FileDetails {
-                    name: file.name.to_string(),
-                    hash: file.hash.to_string(),
-                    size: file.size,
-                };
-                game_manifest.files.push(extracted_file_details.clone());
+                game_manifest.files.push(file.clone());
                 remove_duplicates(&mut game_manifest);
-                save_manifest(&file_location_download, &game_manifest)?;
+                save_manifest(&manifest_location, &game_manifest)?;
                 //update_local_manifest(&file_location_download, &extracted_file_details, game_id, &game_title, &game_version, game_binary_size)?;
                 total_downloaded += file.size;
                 bytes_downloaded += file.size;
             }
         } else {
             // Si le fichier doit être placé dans un sous-dossier, ajustez le chemin
-            let target_path = game_directory.join(&file.name);
+            let target_path = write_directory.join(&file.name);
             if let Some(parent) = target_path.parent() {
-                if parent != game_directory {
+                if parent != write_directory {
                     println!("Creating directory for nested file: {}", parent.display());
                     fs::create_dir_all(parent).map_err(|e| format!("Failed to create nested directory: {}", e))?;
                 }
@@ -429,6 +1147,7 @@ async fn download_and_update_game(
             let temp_file_path = target_path.with_extension("tmp");
             println!("Writing file to temporary path: {}", temp_file_path.display());
             fs::write(&temp_file_path, &file_data).map_err(|e| format!("Failed to write file: {}", e))?;
+            apply_unix_mode(&temp_file_path, file.unix_mode())?;
             fs::rename(&temp_file_path, &target_path).map_err(|e| format!("Failed to rename temp file: {}", e))?;
 
             // Validation du fichier téléchargé (comparaison des hash)
@@ -446,18 +1165,47 @@ async fn download_and_update_game(
     // Supprimer les doublons dans le manifeste local
     remove_duplicates(&mut game_manifest);
 
+    // Tous les fichiers préparés sont vérifiés : bascule vers la nouvelle version
+    if staged_update {
+        let live_manifest = read_local_manifest(&file_location_download)?.unwrap_or_default();
+        let obsolete_files = live_manifest.files.iter()
+            .filter(|live_file| !game_manifest.files.iter().any(|file| file.name == live_file.name))
+            .filter(|live_file| !is_preserved_path(&live_file.name, &game_manifest.preservedPaths))
+            .map(|live_file| live_file.name.clone())
+            .collect();
+        let staged_files = game_manifest.files.iter()
+            .filter(|file| write_directory.join(&file.name).exists())
+            .map(|file| file.name.clone())
+            .collect();
+        game_manifest.pathInstallLocation = file_location_download.clone();
+
+        let journal = StagingJournal {
+            manifest: game_manifest.clone(),
+            stagedFiles: staged_files,
+            obsoleteFiles: obsolete_files,
+            previousManifest: previous_manifest,
+        };
+        write_staging_journal(game_directory, &journal)?;
+        switch_staged_update(game_directory, &journal)?;
+    }
+
     // Sauvegarder le manifeste mis à jour après le téléchargement
     save_manifest(&file_location_download, &game_manifest)?;
+    enforce_previous_version_max_size(game_directory, launcher_settings.previousVersionMaxSize)?;
 
     // Appeler la fonction de nettoyage après avoir sauvegardé le manifeste
     // Pour supprimer les fichiers que l'utilisateur aurait pus ajouté manuellement
     clean_up_directory(&game_directory, &game_manifest)?;
 
-    // Création d'un raccourci sur le bureau si nécessaire
-    if desktop_shortcut {
-        create_shortcut(file_location_download.clone()).map_err(|e| format!("Failed to create shortcut: {}", e))?;
+    // Création des raccourcis si nécessaire : options détaillées, ou raccourci par défaut avec desktop_shortcut
+    let shortcut_options = shortcut_options.or_else(|| desktop_shortcut.then(ShortcutOptions::default));
+    if let Some(shortcut_options) = shortcut_options {
+        apply_game_shortcuts(webview.app_handle(), game_directory, shortcut_through_launcher.unwrap_or(false), shortcut_options)
+            .map_err(|e| format!("Failed to create shortcut: {}", e))?;
     }
 
+    register_installed_game(webview.app_handle(), &game_manifest)?;
+
     // Émettre un événement de fin de téléchargement
     webview.emit("game-installation-complete", Some(json!({
         "gameTitle": game_title,
@@ -472,14 +1220,13 @@ async fn download_and_update_game(
     Ok(())
 }
 
+// Hash SHA-256 du fichier, lu par blocs pour ne pas charger les gros fichiers en mémoire
 fn calculate_file_hash(file_path: &Path) -> Result<String, String> {
     let file = fs::File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
     let mut reader = BufReader::new(file);
     let mut hasher = Sha256::new();
-    let mut buffer = Vec::new();
 
-    reader.read_to_end(&mut buffer).map_err(|e| format!("Failed to read file: {}", e))?;
-    hasher.update(buffer);
+    std::io::copy(&mut reader, &mut hasher).map_err(|e| format!("Failed to read file: {}", e))?;
 
     Ok(format!("{:x}", hasher.finalize()))
 }
@@ -491,10 +1238,7 @@ fn load_or_create_manifest(
     game_binary_size: u64,
     game_version: String,
 ) -> Result<GameManifestLocal, String> {
-    let manifest_path = format!("{}/manifest_local.json", file_location_download);
-    if Path::new(&manifest_path).exists() {
-        let manifest_content = fs::read_to_string(&manifest_path).map_err(|e| e.to_string())?;
-        let manifest: GameManifestLocal = serde_json::from_str(&manifest_content).map_err(|e| e.to_string())?;
+    if let Some(manifest) = read_local_manifest(file_location_download)? {
         Ok(manifest)
     } else {
         Ok(GameManifestLocal {
@@ -504,10 +1248,19 @@ fn load_or_create_manifest(
             gameBinarySize: game_binary_size,
             version: game_version,
             files: vec![],
+            ..Default::default()
         })
     }
 }
 
+// Reprend dans le manifeste local les réglages du manifeste distant (lancement, données du joueur...)
+fn apply_remote_manifest_settings(local_manifest: &mut GameManifestLocal, remote_manifest: &GameManifestRemote) {
+    local_manifest.launchArguments = remote_manifest.launchArguments.clone();
+    local_manifest.executable = remote_manifest.executable_for_current_platform();
+    local_manifest.allowMultipleInstances = remote_manifest.allowMultipleInstances;
+    local_manifest.preservedPaths = remote_manifest.preservedPaths.clone();
+}
+
 fn save_manifest(file_location_download: &str, manifest: &GameManifestLocal) -> Result<(), String> {
     let manifest_path = format!("{}/manifest_local.json", file_location_download);
     let updated_manifest = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
@@ -531,17 +1284,158 @@ struct FileDetails {
     name: String,
     hash: String,
     size: u64,
+    // Mode Unix du fichier (ex: 0o755 = 493), prioritaire sur `executable`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mode: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    executable: Option<bool>,
+    // Composant optionnel (DLC, pack de contenu) auquel appartient le fichier, None pour le jeu de base
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    component: Option<String>,
+    // Langues du fichier (ex: voix "fr-FR"), vide pour un fichier commun à toutes les langues
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    locales: Vec<String>,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[allow(non_snake_case)]
-struct GameManifestLocal {
-    pathInstallLocation: String,
-    gameId: u64,
+impl FileDetails {
+    // Mode Unix à appliquer au fichier téléchargé, None pour garder les droits par défaut
+    fn unix_mode(&self) -> Option<u32> {
+        match (self.mode, self.executable) {
+            (Some(mode), _) => Some(mode & 0o777),
+            (None, Some(true)) => Some(0o755),
+            _ => None,
+        }
+    }
+
+    fn is_in_components(&self, components: &[String]) -> bool {
+        self.component.as_ref().is_none_or(|component| components.contains(component))
+    }
+
+    // None : toutes les langues sont installées
+    fn is_in_languages(&self, languages: Option<&[String]>) -> bool {
+        self.locales.is_empty() || languages.is_none_or(|languages| {
+            self.locales.iter().any(|locale| languages.iter().any(|language| language.eq_ignore_ascii_case(locale)))
+        })
+    }
+
+    #[cfg(unix)]
+    fn is_executable(&self) -> bool {
+        self.unix_mode().is_some_and(|mode| mode & 0o111 != 0)
+    }
+}
+
+// Applique le mode Unix déclaré par le manifest (sans effet sous Windows)
+fn apply_unix_mode(path: &Path, mode: Option<u32>) -> Result<(), String> {
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .map_err(|e| format!("Failed to set permissions on {}: {}", path.display(), e))?;
+    }
+
+    #[cfg(not(unix))]
+    let _ = (path, mode);
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct GameManifestLocal {
+    pathInstallLocation: String,
+    gameId: u64,
     gameTitle: String,
     gameBinarySize: u64,
     version: String,
     files: Vec<FileDetails>,
+    // Arguments de lancement par défaut fournis par le serveur
+    #[serde(default)]
+    launchArguments: Vec<String>,
+    // Exécutable principal déclaré par le manifest distant pour cette plateforme (relatif au dossier du jeu)
+    #[serde(default)]
+    executable: Option<String>,
+    // Le jeu peut être lancé plusieurs fois en même temps
+    #[serde(default)]
+    allowMultipleInstances: bool,
+    // Données du joueur à ne jamais supprimer (ex: "saves/**", "*.ini")
+    #[serde(default)]
+    preservedPaths: Vec<String>,
+    // Canal de version installé (stable, beta, branche)
+    #[serde(default)]
+    channel: ReleaseChannel,
+    // Composants optionnels installés (dépendances comprises), None si le joueur n'a jamais choisi
+    #[serde(default)]
+    components: Option<Vec<String>>,
+    // Langues installées, None si toutes les langues sont installées
+    #[serde(default)]
+    languages: Option<Vec<String>>,
+}
+
+const STABLE_CHANNEL: &str = "stable";
+
+// Canal de version d'un jeu : "stable", "beta" ou une branche, avec une clé d'accès pour les branches privées
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct ReleaseChannel {
+    name: String,
+    // Jamais écrite dans le dossier du jeu, gardée dans channelAccessKeys.json
+    #[serde(default, skip_serializing)]
+    accessKey: Option<String>,
+}
+
+impl Default for ReleaseChannel {
+    fn default() -> Self {
+        ReleaseChannel { name: STABLE_CHANNEL.to_string(), accessKey: None }
+    }
+}
+
+impl ReleaseChannel {
+    fn validate(&self) -> Result<(), String> {
+        let is_valid_name = !self.name.is_empty()
+            && self.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            && !self.name.starts_with('.');
+        if is_valid_name {
+            Ok(())
+        } else {
+            Err(format!("Invalid release channel name: {}", self.name))
+        }
+    }
+
+    // Dossier d'une version du jeu dans le bucket : le canal stable garde l'organisation historique
+    // "<pathFilename><version>/<architecture>/", les autres canaux sont dans "<pathFilename>channels/<canal>/"
+    fn build_path(&self, path_filename: &str, game_version: &str, os_architecture: &str) -> String {
+        if self.name == STABLE_CHANNEL {
+            format!("{}{}/{}/", path_filename, game_version, os_architecture)
+        } else {
+            format!("{}channels/{}/{}/{}/", path_filename, self.name, game_version, os_architecture)
+        }
+    }
+
+    // Paramètre ajouté aux requêtes de l'API pour les branches privées
+    fn access_key_query(&self) -> String {
+        self.accessKey.as_deref()
+            .filter(|access_key| !access_key.is_empty())
+            .map(|access_key| format!("&accessKey={}", url::form_urlencoded::byte_serialize(access_key.as_bytes()).collect::<String>()))
+            .unwrap_or_default()
+    }
+}
+
+// Clés d'accès des branches privées par jeu et par canal (channelAccessKeys.json)
+type ChannelAccessKeys = HashMap<u64, HashMap<String, String>>;
+
+// Enregistre la clé fournie avec le canal, sinon reprend celle déjà enregistrée pour ce canal
+fn resolve_channel_access_key(app: &tauri::AppHandle, game_id: u64, channel: &mut ReleaseChannel) -> Result<(), String> {
+    let mut access_keys: ChannelAccessKeys = load_launcher_data(app, "channelAccessKeys.json")?;
+    match channel.accessKey.as_deref().filter(|access_key| !access_key.is_empty()) {
+        Some(access_key) => {
+            access_keys.entry(game_id).or_default().insert(channel.name.clone(), access_key.to_string());
+            save_launcher_data(app, "channelAccessKeys.json", &access_keys)?;
+        }
+        None => {
+            channel.accessKey = access_keys.get(&game_id).and_then(|keys| keys.get(&channel.name)).cloned();
+        }
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -549,6 +1443,201 @@ struct GameManifestLocal {
 struct GameManifestRemote {
     version: String,
     files: Vec<FileDetails>,
+    #[serde(default)]
+    launchArguments: Vec<String>,
+    // Exécutables principaux du jeu par OS / architecture
+    #[serde(default)]
+    executables: Vec<ExecutableDeclaration>,
+    #[serde(default)]
+    allowMultipleInstances: bool,
+    #[serde(default)]
+    preservedPaths: Vec<String>,
+    // Composants optionnels du jeu, les fichiers y sont rattachés par FileDetails.component
+    #[serde(default)]
+    components: Vec<GameComponent>,
+    // Langue installée si le joueur n'en choisit pas, toutes les langues si absente
+    #[serde(default)]
+    defaultLanguage: Option<String>,
+}
+
+// Composant optionnel d'un jeu (textures HD, pack de langue, DLC)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct GameComponent {
+    id: String,
+    name: String,
+    // Composants installés avec celui-ci
+    #[serde(default)]
+    dependencies: Vec<String>,
+    // Installé si le joueur ne choisit pas ses composants
+    #[serde(default)]
+    default: bool,
+}
+
+// Déclaration de l'exécutable principal pour un OS (et optionnellement une architecture)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct ExecutableDeclaration {
+    os: String,
+    #[serde(default)]
+    architecture: Option<String>,
+    // Chemin relatif au dossier du jeu (ex: "bin/Game.exe", "Game.app", "Game.AppImage")
+    path: String,
+}
+
+impl GameManifestRemote {
+    // Composants demandés avec leurs dépendances (ceux proposés par défaut si None), dans l'ordre du manifest
+    fn resolve_components(&self, requested: Option<&[String]>) -> Result<Vec<String>, String> {
+        let mut pending: Vec<String> = match requested {
+            Some(requested) => requested.to_vec(),
+            None => self.components.iter().filter(|component| component.default).map(|component| component.id.clone()).collect(),
+        };
+
+        let mut selected = HashSet::new();
+        while let Some(component_id) = pending.pop() {
+            let component = self.components.iter()
+                .find(|component| component.id == component_id)
+                .ok_or_else(|| format!("Unknown game component: {}", component_id))?;
+            if selected.insert(component_id) {
+                pending.extend(component.dependencies.iter().cloned());
+            }
+        }
+
+        Ok(self.components.iter().map(|component| component.id.clone()).filter(|id| selected.contains(id)).collect())
+    }
+
+    // Composants installés encore proposés par le manifest (un composant retiré par le serveur est ignoré)
+    fn installed_components(&self, local_manifest: &GameManifestLocal) -> Result<Vec<String>, String> {
+        let known: Option<Vec<String>> = local_manifest.components.as_ref().map(|components| {
+            components.iter()
+                .filter(|component_id| self.components.iter().any(|component| &component.id == *component_id))
+                .cloned()
+                .collect()
+        });
+        self.resolve_components(known.as_deref())
+    }
+
+    // Composants dont des fichiers sont déjà sur le disque (import), avec leurs dépendances : ceux proposés par défaut si aucun
+    fn detect_components(&self, present_files: &[FileDetails]) -> Result<Vec<String>, String> {
+        let detected: Vec<String> = self.components.iter()
+            .filter(|component| present_files.iter().any(|file| file.component.as_ref() == Some(&component.id)))
+            .map(|component| component.id.clone())
+            .collect();
+        if detected.is_empty() {
+            self.resolve_components(None)
+        } else {
+            self.resolve_components(Some(&detected))
+        }
+    }
+
+    // Manifest réduit au jeu de base et aux composants sélectionnés
+    fn for_components(&self, components: &[String]) -> GameManifestRemote {
+        let mut manifest = self.clone();
+        manifest.files.retain(|file| file.is_in_components(components));
+        manifest
+    }
+
+    // Langues proposées par les fichiers du manifest, triées
+    fn available_languages(&self) -> Vec<String> {
+        let mut languages: Vec<String> = self.files.iter().flat_map(|file| file.locales.iter().cloned()).collect();
+        languages.sort();
+        languages.dedup();
+        languages
+    }
+
+    // Langues demandées, sinon la langue par défaut du manifest (None : toutes les langues)
+    fn resolve_languages(&self, requested: Option<&[String]>) -> Result<Option<Vec<String>>, String> {
+        let Some(requested) = requested else {
+            return Ok(self.defaultLanguage.clone().map(|language| vec![language]));
+        };
+
+        if requested.is_empty() {
+            return Err("At least one language must be selected".to_string());
+        }
+        let available_languages = self.available_languages();
+        if let Some(unknown) = requested.iter().find(|language| !available_languages.iter().any(|available| available.eq_ignore_ascii_case(language))) {
+            return Err(format!("Unknown game language: {}", unknown));
+        }
+        Ok(Some(requested.to_vec()))
+    }
+
+    // Langues installées encore proposées par le manifest, la langue par défaut si aucune ne l'est plus
+    fn installed_languages(&self, local_manifest: &GameManifestLocal) -> Result<Option<Vec<String>>, String> {
+        let available_languages = self.available_languages();
+        let known: Option<Vec<String>> = local_manifest.languages.as_ref()
+            .map(|languages| {
+                languages.iter()
+                    .filter(|language| available_languages.iter().any(|available| available.eq_ignore_ascii_case(language)))
+                    .cloned()
+                    .collect::<Vec<String>>()
+            })
+            .filter(|languages| !languages.is_empty());
+        if local_manifest.languages.is_some() && known.is_none() {
+            return self.resolve_languages(None);
+        }
+        Ok(known)
+    }
+
+    // Langues dont des fichiers sont déjà sur le disque (import) : None si toutes le sont, la langue par défaut si aucune
+    fn detect_languages(&self, present_files: &[FileDetails]) -> Result<Option<Vec<String>>, String> {
+        let available_languages = self.available_languages();
+        let detected: Vec<String> = available_languages.iter()
+            .filter(|language| present_files.iter().any(|file| file.locales.iter().any(|locale| locale.eq_ignore_ascii_case(language))))
+            .cloned()
+            .collect();
+        if detected.len() == available_languages.len() {
+            Ok(None)
+        } else if detected.is_empty() {
+            self.resolve_languages(None)
+        } else {
+            Ok(Some(detected))
+        }
+    }
+
+    // Manifest réduit aux fichiers communs et à ceux des langues sélectionnées
+    fn for_languages(&self, languages: Option<&[String]>) -> GameManifestRemote {
+        let mut manifest = self.clone();
+        manifest.files.retain(|file| file.is_in_languages(languages));
+        manifest
+    }
+
+    // Exécutable déclaré pour l'OS / l'architecture courante, une déclaration avec
+    // l'architecture exacte est prioritaire sur une déclaration sans architecture
+    fn executable_for_current_platform(&self) -> Option<String> {
+        let system_os_info = get_system_os_info_current();
+        let current_architecture = normalize_architecture(&system_os_info.architecture);
+
+        let for_current_os = self.executables.iter()
+            .filter(|declaration| declaration.os.eq_ignore_ascii_case(&system_os_info.os))
+            .filter(|declaration| is_safe_relative_path(&declaration.path));
+
+        let mut fallback = None;
+        for declaration in for_current_os {
+            match &declaration.architecture {
+                Some(architecture) if normalize_architecture(architecture) == current_architecture => {
+                    return Some(declaration.path.clone());
+                }
+                None if fallback.is_none() => fallback = Some(declaration.path.clone()),
+                _ => {}
+            }
+        }
+
+        fallback
+    }
+}
+
+// Options de lancement d'un jeu configurées par le joueur, elles complètent
+// les arguments par défaut du manifest
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct LaunchOptions {
+    #[serde(default)]
+    arguments: Vec<String>,
+    #[serde(default)]
+    environmentVariables: HashMap<String, String>,
+    // Dossier de travail, relatif au dossier du jeu ou absolu (par défaut le dossier du jeu)
+    #[serde(default)]
+    workingDirectory: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -561,285 +1650,2262 @@ struct GameProgressDownload {
     version: String
 }
 
+// Exécutable contenu dans un bundle .app (Contents/MacOS), par ordre alphabétique
+fn find_executable_in_app_bundle(bundle_path: &Path) -> Result<String, String> {
+    let app_executable_path = bundle_path.join("Contents/MacOS");
+    let mut app_entries = Vec::new();
+    for app_entry in fs::read_dir(&app_executable_path).map_err(|e| format!("Failed to read app directory: {}", e))? {
+        let app_entry = app_entry.map_err(|e| format!("Failed to read app entry: {}", e))?;
+        app_entries.push(app_entry.path());
+    }
+    app_entries.sort();
+
+    app_entries.into_iter()
+        .find(|app_path| app_path.is_file() && app_path.extension().is_none())
+        .map(|app_path| app_path.to_string_lossy().into_owned())
+        .ok_or_else(|| format!("No executable found in app bundle: {:?}", bundle_path))
+}
+
+// Exécutable du jeu : celui déclaré dans manifest_local.json s'il existe,
+// sinon recherche heuristique dans le dossier du jeu
+fn resolve_game_executable(game_directory: &Path) -> Result<String, String> {
+    let declared_executable = read_local_manifest(&game_directory.to_string_lossy())?
+        .and_then(|manifest| manifest.executable)
+        .filter(|executable| is_safe_relative_path(executable));
+
+    if let Some(executable) = declared_executable {
+        let executable_path = game_directory.join(&executable);
+        if executable_path.is_dir() && executable_path.extension().and_then(|ext| ext.to_str()) == Some("app") {
+            return find_executable_in_app_bundle(&executable_path);
+        }
+        if executable_path.is_file() {
+            return Ok(executable_path.to_string_lossy().into_owned());
+        }
+        println!("Declared executable not found: {}, falling back to discovery", executable_path.display());
+    }
+
+    find_executable_in_directory(game_directory)
+}
+
 fn find_executable_in_directory(directory_path: &Path) -> Result<String, String> {
     if !directory_path.exists() || !directory_path.is_dir() {
         return Err(format!("Invalid directory path: {:?}", directory_path));
     }
 
+    // Ordre déterministe : les fichiers du dossier courant (triés par nom) avant les sous-dossiers
+    let mut entries = Vec::new();
     for entry in fs::read_dir(directory_path).map_err(|e| format!("Failed to read directory: {}", e))? {
         let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let path = entry.path();
+        entries.push(entry.path());
+    }
+    entries.sort_by(|a, b| a.is_dir().cmp(&b.is_dir()).then_with(|| a.file_name().cmp(&b.file_name())));
 
+    for path in entries {
         if path.is_dir() {
             #[cfg(target_os = "macos")]
             {
                 // Vérifiez si le répertoire est un bundle .app
                 if path.extension().and_then(|ext| ext.to_str()) == Some("app") {
-                    let app_executable_path = path.join("Contents/MacOS");
-                    if app_executable_path.exists() {
-                        for app_entry in fs::read_dir(&app_executable_path).map_err(|e| format!("Failed to read app directory: {}", e))? {
-                            let app_entry = app_entry.map_err(|e| format!("Failed to read app entry: {}", e))?;
-                            let app_path = app_entry.path();
-                            if app_path.is_file() && app_path.extension().is_none() {
-                                return Ok(app_path.to_string_lossy().into_owned());
-                            }
-                        }
+                    if let Ok(app_executable) = find_executable_in_app_bundle(&path) {
+                        return Ok(app_executable);
+                    }
+                }
+            }
+
+            if let Ok(executable) = find_executable_in_directory(&path) {
+                return Ok(executable);
+            }
+        } else if EXECUTABLE_EXTENSIONS.iter().any(|&ext| path.extension().map_or(false, |p_ext| p_ext == ext)) {
+            // Sous Linux, un .AppImage doit aussi être un vrai binaire
+            if cfg!(target_os = "linux") && !is_native_executable_file(&path) {
+                continue;
+            }
+            return Ok(path.to_string_lossy().into_owned());
+        } else if cfg!(target_os = "linux") && path.extension().is_none() && is_native_executable_file(&path) {
+            // Fichier sans extension sous Linux : binaire ELF ou script déjà exécutable
+            return Ok(path.to_string_lossy().into_owned());
+        }
+    }
+
+    Err("No executable found in the directory".to_string())
+}
+
+// Vérifie l'en-tête du fichier (ELF ou shebang) et, sous Unix, que les droits
+// d'exécution sont déjà présents : aucun droit n'est modifié ici
+fn is_native_executable_file(path: &Path) -> bool {
+    let mut header = [0u8; 4];
+    let read = match fs::File::open(path).and_then(|mut file| file.read(&mut header)) {
+        Ok(read) => read,
+        Err(_) => return false,
+    };
+    let header = &header[..read];
+    if header != b"\x7fELF" && !header.starts_with(b"#!") {
+        return false;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path).map(|metadata| metadata.permissions().mode() & 0o111 != 0).unwrap_or(false)
+    }
+
+    #[cfg(not(unix))]
+    {
+        true
+    }
+}
+
+// Vérifie si le chemin correspond à l'exécutable déclaré dans manifest_local.json
+// (ou se trouve dans le bundle .app déclaré) ou à un fichier marqué exécutable
+#[cfg(unix)]
+fn is_manifest_executable(game_directory: &Path, path: &Path) -> Result<bool, String> {
+    let manifest = match read_local_manifest(&game_directory.to_string_lossy())? {
+        Some(manifest) => manifest,
+        None => return Ok(false),
+    };
+
+    let is_declared_executable = manifest.executable
+        .filter(|executable| is_safe_relative_path(executable))
+        .is_some_and(|executable| path.starts_with(game_directory.join(executable)));
+    let is_executable_file = manifest.files.iter()
+        .any(|file| file.is_executable() && game_directory.join(&file.name) == path);
+
+    Ok(is_declared_executable || is_executable_file)
+}
+
+// Ajoute les droits d'exécution à l'exécutable du jeu si nécessaire.
+// Sous Linux seul un exécutable déclaré par le manifest peut être modifié, sous macOS
+// l'exécutable provient toujours du dossier Contents/MacOS d'un bundle .app
+#[cfg(unix)]
+fn ensure_executable_permissions(game_directory: &Path, executable_path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(executable_path)
+        .map_err(|e| format!("Failed to get metadata: {}", e))?
+        .permissions();
+    if permissions.mode() & 0o111 != 0 {
+        return Ok(());
+    }
+
+    if cfg!(target_os = "linux") && !is_manifest_executable(game_directory, executable_path)? {
+        return Err(format!("{} is not executable and is not declared as executable by the manifest", executable_path.display()));
+    }
+
+    permissions.set_mode(permissions.mode() | 0o111); // chmod +x
+    fs::set_permissions(executable_path, permissions).map_err(|e| format!("Failed to set permissions: {}", e))
+}
+
+// Cible d'un raccourci qui passe par le launcher (mise à jour, vérification et suivi du jeu
+// avant son lancement) : chemin de l'exécutable du launcher et identifiant du jeu
+fn get_launcher_shortcut_target(game_directory: &Path) -> Result<(String, u64), String> {
+    let manifest = read_local_manifest(&game_directory.to_string_lossy())?
+        .ok_or_else(|| format!("manifest_local.json not found in {}", game_directory.display()))?;
+    let launcher_path = env::current_exe().map_err(|e| format!("Error obtaining current exe path: {}", e))?;
+    Ok((launcher_path.to_string_lossy().into_owned(), manifest.gameId))
+}
+
+// Raccourcis créés par le launcher pour un jeu (shortcuts.json), pour pouvoir les
+// lister, les réparer et les supprimer à la désinstallation
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct GameShortcuts {
+    gameDirectory: String,
+    throughLauncher: bool,
+    files: Vec<String>,
+    #[serde(default)]
+    options: ShortcutOptions,
+    // Entrée ajoutée par le launcher aux favoris du dock, à retirer avec les raccourcis
+    #[serde(default)]
+    pinnedEntry: Option<String>,
+}
+
+// Raccourcis choisis pour un jeu : bureau, menu des applications (menu Démarrer, ~/Applications,
+// $XDG_DATA_HOME/applications) et épinglage au dock quand le système le permet
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct ShortcutOptions {
+    #[serde(default)]
+    desktop: bool,
+    #[serde(default)]
+    applicationsMenu: bool,
+    #[serde(default)]
+    taskbarPin: bool,
+}
+
+impl Default for ShortcutOptions {
+    // Comportement historique de desktop_shortcut : bureau, plus le menu des applications sur Linux
+    fn default() -> Self {
+        ShortcutOptions {
+            desktop: true,
+            applicationsMenu: cfg!(target_os = "linux"),
+            taskbarPin: false,
+        }
+    }
+}
+
+impl ShortcutOptions {
+    fn is_empty(&self) -> bool {
+        !self.desktop && !self.applicationsMenu && !self.taskbarPin
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct ShortcutStatus {
+    path: String,
+    exists: bool,
+}
+
+fn load_all_shortcuts(app: &tauri::AppHandle) -> Result<HashMap<u64, GameShortcuts>, String> {
+    load_launcher_data(app, "shortcuts.json")
+}
+
+fn save_all_shortcuts(app: &tauri::AppHandle, all_shortcuts: &HashMap<u64, GameShortcuts>) -> Result<(), String> {
+    save_launcher_data(app, "shortcuts.json", all_shortcuts)
+}
+
+// Remplace les raccourcis du jeu installé dans game_directory par ceux demandés et enregistre ces choix
+fn apply_game_shortcuts(app: &tauri::AppHandle, game_directory: &Path, through_launcher: bool, options: ShortcutOptions) -> Result<(), String> {
+    let game_id = read_local_manifest(&game_directory.to_string_lossy())?.map(|manifest| manifest.gameId);
+
+    // Les raccourcis précédents restent enregistrés tant que les nouveaux ne sont pas créés,
+    // pour pouvoir encore les supprimer si la création échoue
+    let mut all_shortcuts = load_all_shortcuts(app)?;
+    let previous_shortcuts = game_id.and_then(|game_id| all_shortcuts.get(&game_id).cloned());
+
+    let created_files: Vec<String> = if options.is_empty() {
+        Vec::new()
+    } else {
+        create_game_shortcuts(game_directory, through_launcher, options)?
+            .iter()
+            .map(|file| file.to_string_lossy().into_owned())
+            .collect()
+    };
+
+    // Les fichiers recréés au même endroit sont gardés, les autres anciens raccourcis sont supprimés
+    if let Some(previous_shortcuts) = &previous_shortcuts {
+        let obsolete_files: Vec<String> = previous_shortcuts.files.iter()
+            .filter(|file| !created_files.contains(file))
+            .cloned()
+            .collect();
+        delete_shortcut_files(&obsolete_files);
+        if let Some(pinned_entry) = &previous_shortcuts.pinnedEntry {
+            unpin_game_shortcut(pinned_entry);
+        }
+    }
+
+    let created_paths: Vec<PathBuf> = created_files.iter().map(PathBuf::from).collect();
+    let pinned_entry = if options.taskbarPin { pin_game_shortcut(&created_paths) } else { None };
+
+    let Some(game_id) = game_id else {
+        println!("No manifest_local.json in {}, shortcuts are not recorded", game_directory.display());
+        return Ok(());
+    };
+
+    all_shortcuts.insert(game_id, GameShortcuts {
+        gameDirectory: game_directory.to_string_lossy().into_owned(),
+        throughLauncher: through_launcher,
+        files: created_files,
+        options,
+        pinnedEntry: pinned_entry,
+    });
+    save_all_shortcuts(app, &all_shortcuts)
+}
+
+// Supprime du disque les fichiers d'un raccourci, renvoie ceux qui ont été supprimés
+fn delete_shortcut_files(files: &[String]) -> Vec<String> {
+    let mut removed_files = Vec::new();
+    for file in files {
+        let path = Path::new(file);
+        if !path.exists() && !path.is_symlink() {
+            continue;
+        }
+
+        // Un alias macOS d'un bundle .app peut être vu comme un dossier
+        let result = if path.is_dir() && !path.is_symlink() { fs::remove_dir_all(path) } else { fs::remove_file(path) };
+        match result {
+            Ok(()) => removed_files.push(file.clone()),
+            Err(e) => eprintln!("Failed to remove shortcut: {}: {}", path.display(), e),
+        }
+    }
+    removed_files
+}
+
+fn remove_game_shortcuts(app: &tauri::AppHandle, game_id: u64) -> Result<Vec<String>, String> {
+    let mut all_shortcuts = load_all_shortcuts(app)?;
+    let Some(game_shortcuts) = all_shortcuts.remove(&game_id) else {
+        return Ok(Vec::new());
+    };
+
+    let removed_files = delete_shortcut_files(&game_shortcuts.files);
+    if let Some(pinned_entry) = &game_shortcuts.pinnedEntry {
+        unpin_game_shortcut(pinned_entry);
+    }
+    save_all_shortcuts(app, &all_shortcuts)?;
+    Ok(removed_files)
+}
+
+#[tauri::command]
+fn list_shortcuts(app: tauri::AppHandle, game_id: u64) -> Result<Vec<ShortcutStatus>, String> {
+    let all_shortcuts = load_all_shortcuts(&app)?;
+    let files = all_shortcuts.get(&game_id).map(|game_shortcuts| game_shortcuts.files.clone()).unwrap_or_default();
+
+    Ok(files.into_iter()
+        .map(|file| ShortcutStatus { exists: Path::new(&file).exists(), path: file })
+        .collect())
+}
+
+#[tauri::command]
+fn remove_shortcuts(app: tauri::AppHandle, game_id: u64) -> Result<Vec<String>, String> {
+    remove_game_shortcuts(&app, game_id)
+}
+
+// Recrée les raccourcis du jeu (fichiers supprimés, jeu déplacé...) avec les mêmes options
+#[tauri::command]
+fn repair_shortcuts(app: tauri::AppHandle, game_id: u64) -> Result<Vec<ShortcutStatus>, String> {
+    let all_shortcuts = load_all_shortcuts(&app)?;
+    let game_shortcuts = all_shortcuts.get(&game_id).cloned()
+        .ok_or_else(|| format!("No shortcuts recorded for game {}", game_id))?;
+
+    apply_game_shortcuts(&app, Path::new(&game_shortcuts.gameDirectory), game_shortcuts.throughLauncher, game_shortcuts.options)?;
+
+    list_shortcuts(app, game_id)
+}
+
+#[tauri::command]
+fn get_shortcut_options(app: tauri::AppHandle, game_id: u64) -> Result<Option<ShortcutOptions>, String> {
+    let all_shortcuts = load_all_shortcuts(&app)?;
+    Ok(all_shortcuts.get(&game_id).map(|game_shortcuts| game_shortcuts.options))
+}
+
+// Change les raccourcis d'un jeu déjà installé (ajout ou suppression du bureau, du menu, du dock)
+#[tauri::command]
+fn set_shortcut_options(app: tauri::AppHandle, game_id: u64, options: ShortcutOptions, through_launcher: Option<bool>) -> Result<Vec<ShortcutStatus>, String> {
+    // Jeu installé sans raccourci : le dossier du jeu vient de la bibliothèque
+    let (game_directory, recorded_through_launcher) = match load_all_shortcuts(&app)?.remove(&game_id) {
+        Some(game_shortcuts) => (game_shortcuts.gameDirectory, game_shortcuts.throughLauncher),
+        None => (installed_game_directory(&app, game_id)?, false),
+    };
+
+    let through_launcher = through_launcher.unwrap_or(recorded_through_launcher);
+    apply_game_shortcuts(&app, Path::new(&game_directory), through_launcher, options)?;
+
+    list_shortcuts(app, game_id)
+}
+
+// createShortcut
+#[tauri::command]
+fn create_shortcut(app: tauri::AppHandle, directory_path: String, through_launcher: Option<bool>, options: Option<ShortcutOptions>) -> Result<(), String> {
+    apply_game_shortcuts(&app, Path::new(&directory_path), through_launcher.unwrap_or(false), options.unwrap_or_default())
+}
+
+// Crée les raccourcis du jeu demandés dans options et renvoie les fichiers créés
+fn create_game_shortcuts(directory_path: &Path, through_launcher: bool, options: ShortcutOptions) -> Result<Vec<PathBuf>, String> {
+    let mut created_files = Vec::new();
+    let system_os_info = get_system_os_info_current();
+    let os = &system_os_info.os;
+
+    // Dossiers où placer les raccourcis : bureau et menu des applications
+    #[cfg(not(target_os = "linux"))]
+    let mut shortcut_dirs = Vec::new();
+    #[cfg(not(target_os = "linux"))]
+    if options.desktop {
+        shortcut_dirs.push(get_desktop_path().ok_or_else(|| {
+            let msg = "Failed to get desktop path";
+            println!("{}", msg); // Log error message
+            msg.to_string()
+        })?);
+    }
+    #[cfg(not(target_os = "linux"))]
+    if options.applicationsMenu {
+        shortcut_dirs.push(get_applications_menu_path()?);
+    }
+
+    // Raccourci vers le launcher avec l'argument "--launch <gameId>" au lieu de l'exécutable du jeu
+    let launcher_target = if through_launcher {
+        Some(get_launcher_shortcut_target(directory_path)?)
+    } else {
+        None
+    };
+
+    // L'identifiant du jeu est ajouté aux noms des fichiers créés : deux jeux peuvent avoir un exécutable du même nom
+    let local_manifest = read_local_manifest(&directory_path.to_string_lossy()).ok().flatten();
+    let game_id = local_manifest.as_ref().map(|manifest| manifest.gameId);
+
+    // Pour macOS, nous devons trouver le bundle .app : celui qui contient l'exécutable du jeu
+    // (déclaré par le manifest, sinon trouvé dans un ordre déterministe)
+    #[cfg(target_os = "macos")]
+    let app_bundle_path = {
+        let executable_path = resolve_game_executable(directory_path).map_err(|e| {
+            println!("Failed to find executable: {}", e); // Log error message
+            e
+        })?;
+        Path::new(&executable_path)
+            .ancestors()
+            .take_while(|ancestor| ancestor.starts_with(directory_path) && *ancestor != directory_path)
+            .find(|ancestor| ancestor.extension().is_some_and(|ext| ext == "app"))
+            .map(Path::to_path_buf)
+            .ok_or_else(|| "No .app bundle found in the directory".to_string())?
+    };
+
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    let executable_path = resolve_game_executable(directory_path).map_err(|e| {
+        println!("Failed to find executable: {}", e); // Log error message
+        e
+    })?;
+
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    let exe_name = Path::new(&executable_path)
+        .file_stem()
+        .ok_or("Failed to get executable name")?
+        .to_str()
+        .ok_or("Failed to convert executable name to str")?;
+
+    match os.as_str() {
+        "Windows" => {
+            #[cfg(target_os = "windows")]
+            {
+                // Raccourci LNK for Windows
+                let sl = match &launcher_target {
+                    Some((launcher_path, game_id)) => {
+                        let mut sl = mslnk::ShellLink::new(launcher_path).map_err(|e| format!("Failed to create ShellLink: {}", e))?;
+                        sl.set_arguments(Some(format!("--launch {}", game_id)));
+                        sl.set_icon_location(Some(executable_path.clone()));
+                        sl
                     }
+                    None => mslnk::ShellLink::new(&executable_path).map_err(|e| format!("Failed to create ShellLink: {}", e))?,
+                };
+                for shortcut_dir in &shortcut_dirs {
+                    let shortcut_path = shortcut_dir.join(format!("{}.lnk", shortcut_file_stem(exe_name, game_id)));
+                    sl.create_lnk(shortcut_path.to_string_lossy().into_owned()).map_err(|e| format!("Failed to create lnk: {}", e))?;
+                    created_files.push(shortcut_path);
                 }
             }
+        }
+        "macOS" => {
+            #[cfg(target_os = "macos")]
+            for shortcut_dir in &shortcut_dirs {
+                if let Some((_, game_id)) = &launcher_target {
+                    // Un alias ne peut pas transmettre d'arguments : raccourci .webloc vers l'URL crzgames://
+                    let webloc_name = format!("{}.webloc", shortcut_file_stem(app_bundle_path.file_stem().unwrap().to_str().unwrap(), game_id));
+                    let webloc_content = format!(
+                        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                        <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+                        <plist version=\"1.0\">\n\
+                        <dict>\n\
+                        <key>URL</key>\n\
+                        <string>{}launch/{}</string>\n\
+                        </dict>\n\
+                        </plist>\n",
+                        DEEP_LINK_SCHEME,
+                        game_id
+                    );
+                    let webloc_path = shortcut_dir.join(&webloc_name);
+                    fs::write(&webloc_path, webloc_content)
+                        .map_err(|e| format!("Failed to create webloc file: {}", e))?;
+                    created_files.push(webloc_path);
+                } else {
+                    // Raccourci ALIAS for macOS
+                    let alias_name = format!("{}.alias", shortcut_file_stem(app_bundle_path.file_stem().unwrap().to_str().unwrap(), game_id));
+                    let alias_path = shortcut_dir.join(&alias_name);
+                    // Finder ne remplace pas un alias existant (raccourci réparé ou recréé)
+                    if alias_path.exists() {
+                        fs::remove_file(&alias_path).map_err(|e| format!("Failed to replace alias: {}", e))?;
+                    }
+                    let apple_script = format!(
+                        "tell application \"Finder\" to make alias file to POSIX file \"{}\" at POSIX file \"{}\" with properties {{name:\"{}\"}}",
+                        app_bundle_path.to_str().unwrap(), // Utilise le chemin du bundle .app
+                        shortcut_dir.to_str().unwrap(),
+                        alias_name
+                    );
+
+                    // Execute AppleScript
+                    Command::new("osascript")
+                        .arg("-e")
+                        .arg(&apple_script)
+                        .output()
+                        .map_err(|e| format!("Failed to create alias: {}", e))?;
+                    created_files.push(alias_path);
+                }
+            }
+        }
+        "Linux" => {
+            #[cfg(target_os = "linux")]
+            {
+                // Commande du raccourci : le jeu directement ou le launcher avec l'identifiant du jeu
+                let exec_command = match &launcher_target {
+                    Some((launcher_path, game_id)) => format!("{} --launch {}", desktop_exec_quote(launcher_path), game_id),
+                    None => desktop_exec_quote(&executable_path),
+                };
+
+                // Nom affiché : titre du jeu s'il est connu, sinon le nom de l'exécutable
+                let entry_name = local_manifest
+                    .map(|manifest| manifest.gameTitle)
+                    .filter(|game_title| !game_title.is_empty())
+                    .unwrap_or_else(|| exe_name.to_string());
+
+                let xdg_dirs = XdgDirs::from_env()?;
+                let icon_source_path = directory_path.join(format!("{}.png", exe_name));
+                // L'épinglage au dock utilise l'entrée du menu des applications
+                let applications_menu = options.applicationsMenu || options.taskbarPin;
+                let desktop_file_name = linux_desktop_file_name(exe_name, game_id);
+                created_files.extend(create_linux_shortcuts(&xdg_dirs, &desktop_file_name, &entry_name, &exec_command, &icon_source_path, options.desktop, applications_menu)?);
+            }
+        }
+        _ => return Err("Unsupported OS".to_string()),
+    }
+
+    Ok(created_files)
+}
+
+// Nom affiché d'un raccourci (.lnk, .webloc, alias) : "<exécutable> (<gameId>)"
+#[cfg(not(target_os = "linux"))]
+fn shortcut_file_stem(name: &str, game_id: Option<u64>) -> String {
+    match game_id {
+        Some(game_id) => format!("{} ({})", name, game_id),
+        None => name.to_string(),
+    }
+}
+
+// Nom du fichier .desktop et de l'icône sous Linux : "<exécutable>-<gameId>"
+#[cfg(target_os = "linux")]
+fn linux_desktop_file_name(exe_name: &str, game_id: Option<u64>) -> String {
+    match game_id {
+        Some(game_id) => format!("{}-{}", exe_name, game_id),
+        None => exe_name.to_string(),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_desktop_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|path| path.join("Desktop"))
+}
+
+// Menu Démarrer de l'utilisateur sous Windows, ~/Applications (Launchpad) sous macOS
+#[cfg(not(target_os = "linux"))]
+fn get_applications_menu_path() -> Result<PathBuf, String> {
+    #[cfg(target_os = "windows")]
+    let applications_menu_path = dirs::data_dir()
+        .map(|path| path.join("Microsoft").join("Windows").join("Start Menu").join("Programs").join("CrzGames"));
+    #[cfg(not(target_os = "windows"))]
+    let applications_menu_path = dirs::home_dir().map(|path| path.join("Applications"));
+
+    let applications_menu_path = applications_menu_path.ok_or("Failed to get applications menu path")?;
+    fs::create_dir_all(&applications_menu_path)
+        .map_err(|e| format!("Failed to create directory: {}: {}", applications_menu_path.display(), e))?;
+    Ok(applications_menu_path)
+}
+
+// Épingle l'entrée du menu des applications aux favoris du dock GNOME et renvoie l'entrée ajoutée.
+// Windows et macOS ne permettent pas d'épingler un raccourci par programme : l'option y est ignorée
+fn pin_game_shortcut(created_files: &[PathBuf]) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let applications_dir = XdgDirs::from_env().ok()?.applications_dir();
+        let desktop_id = created_files.iter()
+            .find(|file| file.parent() == Some(applications_dir.as_path()) && file.extension().is_some_and(|ext| ext == "desktop"))?
+            .file_name()?
+            .to_string_lossy()
+            .into_owned();
+        if desktop_id.contains('\'') {
+            return None;
+        }
+
+        // Déjà épinglée par l'utilisateur : elle ne sera pas retirée avec les raccourcis
+        let mut favorites = read_gnome_favorites()?;
+        if favorites.contains(&desktop_id) {
+            return None;
+        }
+        favorites.push(desktop_id.clone());
+        write_gnome_favorites(&favorites).then_some(desktop_id)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = created_files;
+        println!("Pinning shortcuts to the taskbar is not supported on this OS");
+        None
+    }
+}
+
+fn unpin_game_shortcut(pinned_entry: &str) {
+    #[cfg(target_os = "linux")]
+    if let Some(mut favorites) = read_gnome_favorites() {
+        let favorites_count = favorites.len();
+        favorites.retain(|desktop_id| desktop_id != pinned_entry);
+        if favorites.len() != favorites_count {
+            write_gnome_favorites(&favorites);
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    let _ = pinned_entry;
+}
+
+// Favoris du dock GNOME (org.gnome.shell favorite-apps), None si gsettings n'est pas disponible
+#[cfg(target_os = "linux")]
+fn read_gnome_favorites() -> Option<Vec<String>> {
+    let output = match Command::new("gsettings").args(["get", "org.gnome.shell", "favorite-apps"]).output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            println!("gsettings get favorite-apps failed: {}", String::from_utf8_lossy(&output.stderr));
+            return None;
+        }
+        Err(e) => {
+            println!("gsettings is not available, skipping dock pin: {}", e);
+            return None;
+        }
+    };
+
+    // Format GVariant : ['a.desktop', 'b.desktop'] ou @as [] pour une liste vide
+    let value = String::from_utf8_lossy(&output.stdout);
+    let value = value.trim().trim_start_matches("@as").trim();
+    let value = value.strip_prefix('[')?.strip_suffix(']')?;
+    Some(value.split(',')
+        .map(|desktop_id| desktop_id.trim().trim_matches('\'').to_string())
+        .filter(|desktop_id| !desktop_id.is_empty())
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+fn write_gnome_favorites(favorites: &[String]) -> bool {
+    let value = format!("[{}]", favorites.iter().map(|desktop_id| format!("'{}'", desktop_id)).collect::<Vec<_>>().join(", "));
+    match Command::new("gsettings").args(["set", "org.gnome.shell", "favorite-apps", &value]).output() {
+        Ok(output) if output.status.success() => true,
+        Ok(output) => {
+            println!("gsettings set favorite-apps failed: {}", String::from_utf8_lossy(&output.stderr));
+            false
+        }
+        Err(e) => {
+            println!("gsettings is not available: {}", e);
+            false
+        }
+    }
+}
+
+// Icône utilisée quand le jeu ne fournit pas de <exe>.png
+#[cfg(target_os = "linux")]
+const DEFAULT_GAME_ICON: &[u8] = include_bytes!("../icons/128x128.png");
+
+// Tailles des dossiers du thème hicolor, une icône d'une autre taille va dans 256x256 (mise à l'échelle par le thème)
+#[cfg(target_os = "linux")]
+const HICOLOR_ICON_SIZES: [u32; 10] = [16, 22, 24, 32, 48, 64, 96, 128, 256, 512];
+#[cfg(target_os = "linux")]
+const HICOLOR_FALLBACK_ICON_SIZE: u32 = 256;
+
+// Dossiers XDG utilisés pour les raccourcis Linux, résolus à partir d'un HOME donné
+#[cfg(target_os = "linux")]
+struct XdgDirs {
+    desktop_dir: PathBuf,
+    data_home: PathBuf,
+}
+
+#[cfg(target_os = "linux")]
+impl XdgDirs {
+    fn from_env() -> Result<Self, String> {
+        let home = env::var_os("HOME")
+            .map(PathBuf::from)
+            .or_else(dirs::home_dir)
+            .ok_or("Failed to get home directory")?;
+        Ok(Self::resolve(&home, env::var_os("XDG_DATA_HOME"), env::var_os("XDG_CONFIG_HOME")))
+    }
+
+    fn resolve(home: &Path, data_home: Option<std::ffi::OsString>, config_home: Option<std::ffi::OsString>) -> Self {
+        // D'après la spécification XDG, les chemins relatifs sont ignorés
+        let absolute = |value: Option<std::ffi::OsString>| value.map(PathBuf::from).filter(|path| path.is_absolute());
+        let data_home = absolute(data_home).unwrap_or_else(|| home.join(".local/share"));
+        let config_home = absolute(config_home).unwrap_or_else(|| home.join(".config"));
+        let desktop_dir = read_xdg_user_dir(&config_home.join("user-dirs.dirs"), "XDG_DESKTOP_DIR", home)
+            .unwrap_or_else(|| home.join("Desktop"));
+
+        XdgDirs { desktop_dir, data_home }
+    }
+
+    fn applications_dir(&self) -> PathBuf {
+        self.data_home.join("applications")
+    }
+
+    fn hicolor_icon_dir(&self, size: u32) -> PathBuf {
+        self.data_home.join("icons/hicolor").join(format!("{}x{}", size, size)).join("apps")
+    }
+}
+
+// Lit un dossier utilisateur de user-dirs.dirs (ex: XDG_DESKTOP_DIR="$HOME/Bureau")
+#[cfg(target_os = "linux")]
+fn read_xdg_user_dir(user_dirs_file: &Path, key: &str, home: &Path) -> Option<PathBuf> {
+    let content = fs::read_to_string(user_dirs_file).ok()?;
+    let value = content.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| line.strip_prefix(key)?.trim_start().strip_prefix('='))?
+        .trim()
+        .trim_matches('"');
+
+    let path = match value.strip_prefix("$HOME") {
+        Some(relative) => home.join(relative.trim_start_matches('/')),
+        None => PathBuf::from(value),
+    };
+    path.is_absolute().then_some(path)
+}
+
+// Taille d'une icône PNG carrée, lue dans l'en-tête IHDR
+#[cfg(target_os = "linux")]
+fn png_icon_size(data: &[u8]) -> Option<u32> {
+    if data.len() < 24 || &data[..8] != b"\x89PNG\r\n\x1a\n" {
+        return None;
+    }
+
+    let width = u32::from_be_bytes([data[16], data[17], data[18], data[19]]);
+    let height = u32::from_be_bytes([data[20], data[21], data[22], data[23]]);
+    (width == height && width > 0).then_some(width)
+}
+
+// Échappe un argument de la clé Exec d'un fichier .desktop (règles de quoting puis d'échappement des chaînes)
+#[cfg(target_os = "linux")]
+fn desktop_exec_quote(arg: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in arg.chars() {
+        match c {
+            '"' | '`' | '$' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '%' => quoted.push_str("%%"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted.replace('\\', "\\\\")
+}
+
+// Crée le raccourci du bureau et/ou l'entrée du menu des applications, avec l'icône installée
+// dans le thème hicolor de $XDG_DATA_HOME/icons
+#[cfg(target_os = "linux")]
+fn create_linux_shortcuts(
+    xdg_dirs: &XdgDirs,
+    desktop_file_name: &str,
+    entry_name: &str,
+    exec_command: &str,
+    icon_source_path: &Path,
+    desktop: bool,
+    applications_menu: bool,
+) -> Result<Vec<PathBuf>, String> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut created_files = Vec::new();
+
+    // Icône du jeu (<exe>.png) si elle est valide, sinon l'icône par défaut du launcher
+    let icon_data = fs::read(icon_source_path).ok().filter(|data| png_icon_size(data).is_some());
+    let icon_data = icon_data.as_deref().unwrap_or(DEFAULT_GAME_ICON);
+    let icon_size = png_icon_size(icon_data)
+        .filter(|size| HICOLOR_ICON_SIZES.contains(size))
+        .unwrap_or(HICOLOR_FALLBACK_ICON_SIZE);
+    let icon_name = format!("crzgames-{}", desktop_file_name);
+    let icon_dir = xdg_dirs.hicolor_icon_dir(icon_size);
+    fs::create_dir_all(&icon_dir).map_err(|e| format!("Failed to create icons directory: {}", e))?;
+    let icon_path = icon_dir.join(format!("{}.png", icon_name));
+    fs::write(&icon_path, icon_data).map_err(|e| format!("Failed to install icon: {}", e))?;
+    created_files.push(icon_path);
+
+    // Créer le fichier .desktop
+    let desktop_entry = format!(
+        "[Desktop Entry]\n\
+        Name={}\n\
+        Exec={}\n\
+        Icon={}\n\
+        Type=Application\n\
+        Categories=Game;\n\
+        Terminal=false\n",
+        entry_name.replace('\n', " "),
+        exec_command,
+        icon_name
+    );
+
+    let desktop_file_path = xdg_dirs.desktop_dir.join(format!("{}.desktop", desktop_file_name));
+    let applications_file_path = xdg_dirs.applications_dir().join(format!("{}.desktop", desktop_file_name));
+    let entry_paths = [(desktop, &desktop_file_path), (applications_menu, &applications_file_path)];
+    for entry_path in entry_paths.into_iter().filter_map(|(enabled, entry_path)| enabled.then_some(entry_path)) {
+        if let Some(parent) = entry_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}: {}", parent.display(), e))?;
+        }
+        fs::write(entry_path, &desktop_entry)
+            .map_err(|e| format!("Failed to create .desktop file: {}: {}", entry_path.display(), e))?;
+        fs::set_permissions(entry_path, fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to set .desktop file as executable: {}: {}", entry_path.display(), e))?;
+        created_files.push(entry_path.clone());
+    }
+
+    // Activer "Allow Launching" sur le bureau (gio n'est pas disponible sur tous les environnements)
+    if desktop {
+        match Command::new("gio").arg("set").arg(&desktop_file_path).arg("metadata::trusted").arg("true").output() {
+            Ok(output) if !output.status.success() => {
+                println!("gio set metadata::trusted failed: {}", String::from_utf8_lossy(&output.stderr));
+            }
+            Ok(_) => {}
+            Err(e) => println!("gio is not available, skipping metadata::trusted: {}", e),
+        }
+    }
+
+    Ok(created_files)
+}
+
+fn read_local_manifest(file_location_download: &str) -> Result<Option<GameManifestLocal>, String> {
+    let manifest_path = Path::new(file_location_download).join("manifest_local.json");
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+
+    let manifest_content = fs::read_to_string(&manifest_path).map_err(|e| e.to_string())?;
+    let manifest: GameManifestLocal = serde_json::from_str(&manifest_content).map_err(|e| e.to_string())?;
+    Ok(Some(manifest))
+}
+
+fn load_all_launch_options(app: &tauri::AppHandle) -> Result<HashMap<u64, LaunchOptions>, String> {
+    load_launcher_data(app, "launchOptions.json")
+}
+
+fn save_all_launch_options(app: &tauri::AppHandle, all_options: &HashMap<u64, LaunchOptions>) -> Result<(), String> {
+    save_launcher_data(app, "launchOptions.json", all_options)
+}
+
+#[tauri::command]
+fn get_launch_options(app: tauri::AppHandle, game_id: u64) -> Result<LaunchOptions, String> {
+    let all_options = load_all_launch_options(&app)?;
+    Ok(all_options.get(&game_id).cloned().unwrap_or_default())
+}
+
+#[tauri::command]
+fn set_launch_options(app: tauri::AppHandle, game_id: u64, launch_options: LaunchOptions) -> Result<(), String> {
+    if launch_options.environmentVariables.keys().any(|key| key.is_empty() || key.contains('=')) {
+        return Err("Invalid environment variable name".to_string());
+    }
+
+    let mut all_options = load_all_launch_options(&app)?;
+    all_options.insert(game_id, launch_options);
+    save_all_launch_options(&app, &all_options)
+}
+
+#[tauri::command]
+fn reset_launch_options(app: tauri::AppHandle, game_id: u64) -> Result<(), String> {
+    let mut all_options = load_all_launch_options(&app)?;
+    if all_options.remove(&game_id).is_some() {
+        save_all_launch_options(&app, &all_options)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn launch_game(
+    app: tauri::AppHandle,
+    file_location_download: String,
+    verify_files: Option<bool>,
+    remote_version: Option<String>,
+) -> Result<PreLaunchStatus, String> {
+    recover_staged_update(Path::new(&file_location_download))?;
+    let local_manifest = read_local_manifest(&file_location_download)?;
+
+    // Vérification optionnelle avant le lancement, le jeu n'est pas lancé si une mise à jour
+    // ou une réparation est nécessaire
+    let verify_files = verify_files.unwrap_or(false);
+    if verify_files || remote_version.is_some() {
+        let manifest = local_manifest.as_ref()
+            .ok_or_else(|| format!("manifest_local.json not found in {}", file_location_download))?;
+        let status = check_game_before_launch(Path::new(&file_location_download), manifest, remote_version.as_deref(), verify_files);
+        if !matches!(status, PreLaunchStatus::Ok) {
+            return Ok(status);
+        }
+    }
+
+    // Arguments par défaut du manifest local complétés par les options du joueur
+    let launch_options = match &local_manifest {
+        Some(manifest) => load_all_launch_options(&app)?.remove(&manifest.gameId).unwrap_or_default(),
+        None => LaunchOptions::default(),
+    };
+    let allow_multiple_instances = local_manifest.as_ref().is_some_and(|manifest| manifest.allowMultipleInstances);
+    let game_session = local_manifest.as_ref().map(|manifest| (manifest.gameId, manifest.gameTitle.clone()));
+    let mut arguments = local_manifest.map(|manifest| manifest.launchArguments).unwrap_or_default();
+    arguments.extend(launch_options.arguments.iter().cloned());
+    let launch_window_behavior = load_launcher_settings(&app)?.launchWindowBehavior;
+
+    // Un seul lancement à la fois par jeu, sauf si le manifest autorise plusieurs instances
+    let game_session = match game_session {
+        Some((game_id, game_title)) => match register_game_session(game_id, &game_title, allow_multiple_instances) {
+            Some(session) => Some((game_id, session)),
+            None => return Ok(PreLaunchStatus::AlreadyRunning { game_id }),
+        },
+        None => None,
+    };
+
+    // Créer un canal pour transmettre les erreurs
+    let (tx, mut rx) = mpsc::channel(1);
+
+    thread::spawn(move || {
+        let game_dir = Path::new(&file_location_download);
+        let session = game_session.as_ref().map(|(_, session)| session);
+        let result = run_game_process(&app, game_dir, &launch_options, &arguments, session, launch_window_behavior);
+
+        if let Some((game_id, session)) = &game_session {
+            unregister_game_session(*game_id, session);
+            refresh_tray(&app);
+        }
+
+        // Restaurer le launcher quand plus aucun jeu n'est en cours d'exécution
+        if launch_window_behavior != LaunchWindowBehavior::None && RUNNING_GAMES.lock().unwrap().is_empty() {
+            show_main_window(&app);
+        }
+
+        let _ = tx.blocking_send(result);
+    });
+
+    // Recevoir et traiter le résultat sans bloquer le thread principal
+    if let Some(result) = rx.recv().await {
+        return result.map(|_| PreLaunchStatus::Ok);
+    }
+
+    Ok(PreLaunchStatus::Ok)
+}
+
+// Lance l'exécutable du jeu et attend la fin du processus
+fn run_game_process(
+    app: &tauri::AppHandle,
+    game_dir: &Path,
+    launch_options: &LaunchOptions,
+    arguments: &[String],
+    session: Option<&RunningGame>,
+    launch_window_behavior: LaunchWindowBehavior,
+) -> Result<(), String> {
+    // Vérifiez si le répertoire de jeu existe
+    if !game_dir.exists() {
+        return Err(format!("Game directory does not exist: {:?}", game_dir));
+    }
+
+    // Trouver l'exécutable dans le répertoire du jeu
+    let executable_path = resolve_game_executable(game_dir).map_err(|e| format!("Error finding executable: {}", e))?;
+    let game_path = Path::new(&executable_path);
+
+    #[cfg(unix)]
+    ensure_executable_permissions(game_dir, game_path)?;
+
+    // Dossier de travail : celui des options de lancement s'il est défini, sinon le dossier du jeu
+    let working_dir = match &launch_options.workingDirectory {
+        Some(dir) if !dir.is_empty() => game_dir.join(dir),
+        _ => game_dir.to_path_buf(),
+    };
+    if !working_dir.is_dir() {
+        return Err(format!("Working directory does not exist: {:?}", working_dir));
+    }
+
+    // Utiliser std::process::Command pour lancer le jeu et capturer les erreurs
+    use std::process::{Command, Stdio};
+    let mut child = Command::new(game_path)
+        .args(arguments)
+        .envs(&launch_options.environmentVariables)
+        .current_dir(&working_dir)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to launch game: {}", e))?;
+
+    // Lire stderr dans un thread séparé pour ne pas bloquer le jeu quand le buffer est plein
+    let stderr = child.stderr.take();
+    let stderr_reader = thread::spawn(move || {
+        let mut stderr_output = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut stderr_output);
+        }
+        stderr_output
+    });
+
+    // Rattacher le processus à la session pour la barre d'état (menu "Stop" et info-bulle)
+    let child = match session {
+        Some(session) => {
+            // Le joueur a pu demander l'arrêt pendant le démarrage du jeu (vérifié sous le même verrou que stop_game)
+            {
+                let mut session_child = session.child.lock().unwrap();
+                if session.stop_requested.load(Ordering::Relaxed) {
+                    let _ = child.kill();
+                }
+                *session_child = Some(child);
+            }
+            refresh_tray(app);
+            session.child.clone()
+        }
+        None => Arc::new(Mutex::new(Some(child))),
+    };
+
+    // Réduire ou cacher le launcher pendant la partie
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = match launch_window_behavior {
+            LaunchWindowBehavior::Minimize => window.minimize(),
+            LaunchWindowBehavior::Hide => window.hide(),
+            LaunchWindowBehavior::None => Ok(()),
+        };
+    }
+
+    // Attendre la fin du jeu sans garder le verrou, pour pouvoir l'arrêter depuis la barre d'état
+    let status = loop {
+        let wait_result = match child.lock().unwrap().as_mut() {
+            Some(child) => child.try_wait(),
+            None => return Ok(()),
+        };
+        match wait_result {
+            Ok(Some(status)) => break status,
+            Ok(None) => thread::sleep(Duration::from_millis(500)),
+            Err(e) => return Err(format!("Failed to wait for game: {}", e)),
+        }
+    };
+    let stderr_output = stderr_reader.join().unwrap_or_default();
+
+    // Vérifiez si le processus a renvoyé une erreur (sauf s'il a été arrêté par le joueur)
+    let stop_requested = session.is_some_and(|session| session.stop_requested.load(Ordering::Relaxed));
+    if !status.success() && !stop_requested {
+        return Err(format!("Failed to launch game: {}", stderr_output));
+    }
+
+    Ok(())
+}
+
+// Résultat de la désinstallation, chemins relatifs au dossier du jeu
+#[derive(Debug, Clone, Default, serde::Serialize)]
+#[allow(non_snake_case)]
+struct UninstallReport {
+    removedFiles: Vec<String>,
+    // Fichiers qui ne viennent pas du launcher (sauvegardes, captures...), laissés sur le disque
+    leftoverFiles: Vec<String>,
+    // Fichiers correspondant aux chemins à conserver du manifest, laissés sur le disque
+    preservedFiles: Vec<String>,
+    directoryRemoved: bool,
+}
+
+// Dossiers qu'une désinstallation ne doit jamais viser, ni directement ni par un de leurs parents
+fn protected_directories(app: &tauri::AppHandle) -> Vec<PathBuf> {
+    let mut protected_directories = vec![
+        dirs::home_dir(),
+        app.path().app_data_dir().ok(),
+        get_launcher_path_directory().ok().map(PathBuf::from),
+        dirs::desktop_dir(),
+        dirs::document_dir(),
+        dirs::download_dir(),
+        dirs::picture_dir(),
+        dirs::audio_dir(),
+        dirs::video_dir(),
+        dirs::data_dir(),
+        dirs::data_local_dir(),
+        dirs::config_dir(),
+        dirs::cache_dir(),
+    ];
+    protected_directories.extend(["SystemRoot", "ProgramFiles", "ProgramFiles(x86)", "ProgramData"]
+        .iter()
+        .map(|name| env::var_os(name).map(PathBuf::from)));
+
+    protected_directories.into_iter()
+        .flatten()
+        .map(|path| path.canonicalize().unwrap_or(path))
+        .collect()
+}
+
+fn check_uninstall_target(app: &tauri::AppHandle, game_directory: &Path) -> Result<(), String> {
+    if game_directory.parent().is_none() {
+        return Err(format!("Refusing to uninstall a filesystem root: {}", game_directory.display()));
+    }
+
+    match protected_directories(app).iter().find(|protected_directory| protected_directory.starts_with(game_directory)) {
+        Some(protected_directory) => Err(format!(
+            "Refusing to uninstall {}: it contains {}",
+            game_directory.display(),
+            protected_directory.display()
+        )),
+        None => Ok(()),
+    }
+}
+
+// Supprime un fichier installé par le launcher, sans suivre un dossier lien symbolique hors du jeu
+fn remove_owned_file(game_directory: &Path, file_path: &Path, report: &mut UninstallReport) {
+    let Ok(metadata) = fs::symlink_metadata(file_path) else {
+        return;
+    };
+    if metadata.is_dir() {
+        return;
+    }
+
+    let inside_game_directory = file_path.parent()
+        .and_then(|parent| parent.canonicalize().ok())
+        .is_some_and(|parent| parent.starts_with(game_directory));
+    if !inside_game_directory {
+        eprintln!("Skipping file outside of the game directory: {}", file_path.display());
+        return;
+    }
+
+    match fs::remove_file(file_path) {
+        Ok(()) => report.removedFiles.push(relative_display_path(game_directory, file_path)),
+        Err(e) => eprintln!("Failed to remove file: {}: {}", file_path.display(), e),
+    }
+}
+
+fn relative_display_path(game_directory: &Path, path: &Path) -> String {
+    path.strip_prefix(game_directory).unwrap_or(path).to_string_lossy().into_owned()
+}
+
+// Parcourt dir sans suivre les liens symboliques : supprime les fichiers temporaires de téléchargement
+// (.tmp) et les données à conserver si demandé, et liste les autres fichiers, qui n'appartiennent pas au launcher
+fn collect_leftover_files(
+    game_directory: &Path,
+    dir: &Path,
+    preserved_paths: &[String],
+    delete_preserved_files: bool,
+    report: &mut UninstallReport,
+) -> Result<(), String> {
+    let manifest_file_path = game_directory.join("manifest_local.json");
+    for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read directory: {}: {}", dir.display(), e))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+
+        let relative_path = relative_display_path(game_directory, &path);
+        let is_launcher_directory = path == game_directory.join(STAGING_DIR_NAME) || path == game_directory.join(PREVIOUS_VERSION_DIR_NAME);
+        if file_type.is_dir() && is_launcher_directory {
+            match fs::remove_dir_all(&path) {
+                Ok(()) => report.removedFiles.push(relative_path),
+                Err(e) => eprintln!("Failed to remove directory: {}: {}", path.display(), e),
+            }
+        } else if file_type.is_dir() {
+            collect_leftover_files(game_directory, &path, preserved_paths, delete_preserved_files, report)?;
+        } else if path == manifest_file_path {
+            continue;
+        } else if is_preserved_path(&relative_path, preserved_paths) {
+            if delete_preserved_files {
+                remove_owned_file(game_directory, &path, report);
+            } else {
+                report.preservedFiles.push(relative_path);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "tmp") {
+            remove_owned_file(game_directory, &path, report);
+        } else {
+            report.leftoverFiles.push(relative_path);
+        }
+    }
+    Ok(())
+}
+
+// Supprime les dossiers vides sous dir (dir lui-même est conservé), sans suivre les liens symboliques
+fn prune_empty_directories(dir: &Path) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read directory: {}: {}", dir.display(), e))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        if !entry.file_type().map_err(|e| e.to_string())?.is_dir() {
+            continue;
+        }
+
+        let path = entry.path();
+        prune_empty_directories(&path)?;
+        if is_empty_directory(&path) {
+            fs::remove_dir(&path).map_err(|e| format!("Failed to remove directory: {}: {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+fn is_empty_directory(dir: &Path) -> bool {
+    fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_none())
+}
+
+// uninstallGame : ne supprime que les fichiers listés dans manifest_local.json et ceux du launcher,
+// les fichiers ajoutés par l'utilisateur sont laissés en place et signalés dans le rapport.
+// Les chemins à conserver du manifest (sauvegardes...) ne sont supprimés qu'avec delete_preserved_files
+#[tauri::command]
+async fn uninstall_game(app: tauri::AppHandle, path_install_location: String, delete_preserved_files: Option<bool>) -> Result<UninstallReport, String> {
+    let delete_preserved_files = delete_preserved_files.unwrap_or(false);
+    let game_directory = Path::new(&path_install_location);
+    if !game_directory.is_dir() {
+        return Err(format!("Game directory does not exist or is not a directory: {}", path_install_location));
+    }
+    let game_directory = game_directory.canonicalize().map_err(|e| format!("Failed to resolve game directory: {}", e))?;
+    check_uninstall_target(&app, &game_directory)?;
+
+    let manifest = read_local_manifest(&path_install_location)?
+        .ok_or_else(|| format!("manifest_local.json not found in {}, refusing to uninstall", path_install_location))?;
+
+    // Supprimer les raccourcis créés pour le jeu (bureau, menu applications, icônes)
+    if let Err(e) = remove_game_shortcuts(&app, manifest.gameId) {
+        eprintln!("Failed to remove shortcuts: {}", e);
+    }
+
+    let mut report = UninstallReport::default();
+    for file in &manifest.files {
+        if !is_safe_relative_path(&file.name) {
+            eprintln!("Skipping unsafe path in manifest: {}", file.name);
+            continue;
+        }
+
+        // Laissé sur le disque, il sera listé avec les fichiers à conserver
+        if !delete_preserved_files && is_preserved_path(&file.name, &manifest.preservedPaths) {
+            continue;
+        }
+
+        let file_path = game_directory.join(&file.name);
+        remove_owned_file(&game_directory, &file_path, &mut report);
+    }
+
+    collect_leftover_files(&game_directory, &game_directory, &manifest.preservedPaths, delete_preserved_files, &mut report)?;
+    remove_owned_file(&game_directory, &game_directory.join("manifest_local.json"), &mut report);
+    prune_empty_directories(&game_directory)?;
+
+    if is_empty_directory(&game_directory) {
+        fs::remove_dir(&game_directory).map_err(|e| format!("Failed to remove game directory: {}", e))?;
+        report.directoryRemoved = true;
+    }
+
+    unregister_installed_game(&app, manifest.gameId)?;
+    Ok(report)
+}
+
+// Jeu installé, enregistré par le frontend dans gamesInstalled.json à la fin du téléchargement
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct GameInstalled {
+    user_id: u64,
+    gameManifest: GameManifestLocal,
+}
+
+fn load_games_installed(app: &tauri::AppHandle) -> Result<Vec<GameInstalled>, String> {
+    load_launcher_data(app, "gamesInstalled.json")
+}
+
+fn save_games_installed(app: &tauri::AppHandle, games_installed: &Vec<GameInstalled>) -> Result<(), String> {
+    save_launcher_data(app, "gamesInstalled.json", games_installed)
+}
+
+// Remplace le manifeste enregistré pour le jeu (nouvel emplacement, fichiers...)
+fn update_game_installed(app: &tauri::AppHandle, manifest: &GameManifestLocal) -> Result<(), String> {
+    let mut games_installed = load_games_installed(app)?;
+    for game_installed in games_installed.iter_mut().filter(|game_installed| game_installed.gameManifest.gameId == manifest.gameId) {
+        game_installed.gameManifest = manifest.clone();
+    }
+    save_games_installed(app, &games_installed)
+}
+
+// Jeu de la bibliothèque du launcher (library.json), tenue à jour par le téléchargement,
+// la désinstallation, le déplacement et l'import
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[allow(non_snake_case)]
+struct InstalledGame {
+    gameId: u64,
+    gameTitle: String,
+    pathInstallLocation: String,
+    version: String,
+    gameBinarySize: u64,
+    // Date d'installation (timestamp Unix en secondes)
+    installedAt: u64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct LibraryRescanReport {
+    installedGames: Vec<InstalledGame>,
+    // Jeux supprimés en dehors du launcher, retirés de la bibliothèque
+    removedGames: Vec<InstalledGame>,
+}
+
+fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)
+}
+
+impl InstalledGame {
+    fn from_manifest(manifest: &GameManifestLocal, installed_at: u64) -> Self {
+        InstalledGame {
+            gameId: manifest.gameId,
+            gameTitle: manifest.gameTitle.clone(),
+            pathInstallLocation: manifest.pathInstallLocation.clone(),
+            version: manifest.version.clone(),
+            gameBinarySize: manifest.gameBinarySize,
+            installedAt: installed_at,
+        }
+    }
+}
+
+fn load_library(app: &tauri::AppHandle) -> Result<HashMap<u64, InstalledGame>, String> {
+    if get_launcher_data_file(app, "library.json")?.exists() {
+        return load_launcher_data(app, "library.json");
+    }
+
+    // Première utilisation : reprendre les jeux installés connus du frontend
+    let mut library = HashMap::new();
+    for game_installed in load_games_installed(app)? {
+        let manifest = &game_installed.gameManifest;
+        let installed_at = fs::metadata(Path::new(&manifest.pathInstallLocation).join("manifest_local.json"))
+            .and_then(|metadata| metadata.modified())
+            .map(unix_timestamp)
+            .unwrap_or(0);
+        library.insert(manifest.gameId, InstalledGame::from_manifest(manifest, installed_at));
+    }
+    save_library(app, &library)?;
+    Ok(library)
+}
+
+fn save_library(app: &tauri::AppHandle, library: &HashMap<u64, InstalledGame>) -> Result<(), String> {
+    save_launcher_data(app, "library.json", library)
+}
+
+// Ajoute ou met à jour le jeu dans la bibliothèque, en gardant sa date d'installation
+fn register_installed_game(app: &tauri::AppHandle, manifest: &GameManifestLocal) -> Result<(), String> {
+    let mut library = load_library(app)?;
+    let installed_at = library.get(&manifest.gameId)
+        .map(|installed_game| installed_game.installedAt)
+        .unwrap_or_else(|| unix_timestamp(SystemTime::now()));
+    library.insert(manifest.gameId, InstalledGame::from_manifest(manifest, installed_at));
+    save_library(app, &library)
+}
+
+fn unregister_installed_game(app: &tauri::AppHandle, game_id: u64) -> Result<(), String> {
+    let mut library = load_library(app)?;
+    if library.remove(&game_id).is_some() {
+        save_library(app, &library)?;
+    }
+    Ok(())
+}
+
+fn sorted_installed_games(library: HashMap<u64, InstalledGame>) -> Vec<InstalledGame> {
+    let mut installed_games: Vec<InstalledGame> = library.into_values().collect();
+    installed_games.sort_by(|a, b| a.gameTitle.to_lowercase().cmp(&b.gameTitle.to_lowercase()).then(a.gameId.cmp(&b.gameId)));
+    installed_games
+}
+
+#[tauri::command]
+fn list_installed_games(app: tauri::AppHandle) -> Result<Vec<InstalledGame>, String> {
+    Ok(sorted_installed_games(load_library(&app)?))
+}
+
+// Vérifie chaque jeu de la bibliothèque avec son manifest_local.json : met à jour la version et la taille,
+// et retire les jeux dont le dossier ou le manifeste a disparu
+#[tauri::command]
+fn rescan_library(app: tauri::AppHandle) -> Result<LibraryRescanReport, String> {
+    let library = load_library(&app)?;
+    let mut refreshed_library = HashMap::new();
+    let mut removed_games = Vec::new();
+
+    for (game_id, installed_game) in library {
+        match read_local_manifest(&installed_game.pathInstallLocation) {
+            Ok(Some(manifest)) if manifest.gameId == game_id => {
+                let mut refreshed_game = InstalledGame::from_manifest(&manifest, installed_game.installedAt);
+                refreshed_game.pathInstallLocation = installed_game.pathInstallLocation;
+                refreshed_library.insert(game_id, refreshed_game);
+            }
+            _ => removed_games.push(installed_game),
+        }
+    }
+
+    save_library(&app, &refreshed_library)?;
+    Ok(LibraryRescanReport {
+        installedGames: sorted_installed_games(refreshed_library),
+        removedGames: removed_games,
+    })
+}
+
+// Dossier de bibliothèque où sont installés les jeux (ex: un SSD et un HDD)
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct LibraryFolder {
+    path: String,
+    // false pour le dossier par défaut, utilisé tant qu'aucun dossier n'est configuré
+    configured: bool,
+    totalSpace: Option<u64>,
+    availableSpace: Option<u64>,
+    gameIds: Vec<u64>,
+}
+
+fn load_library_folders(app: &tauri::AppHandle) -> Result<Vec<String>, String> {
+    load_launcher_data(app, "libraryFolders.json")
+}
+
+fn save_library_folders(app: &tauri::AppHandle, library_folders: &Vec<String>) -> Result<(), String> {
+    save_launcher_data(app, "libraryFolders.json", library_folders)
+}
+
+// Dossier par défaut de l'utilisateur, le dossier du launcher n'est pas toujours accessible en écriture
+fn default_library_folder(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path().app_local_data_dir()
+        .map(|path| path.join("games"))
+        .map_err(|e| format!("Error obtaining app local data directory: {}", e))
+}
+
+fn describe_library_folders(app: &tauri::AppHandle) -> Result<Vec<LibraryFolder>, String> {
+    let configured_folders = load_library_folders(app)?;
+    let folders: Vec<(PathBuf, bool)> = if configured_folders.is_empty() {
+        vec![(default_library_folder(app)?, false)]
+    } else {
+        configured_folders.iter().map(|folder| (PathBuf::from(folder), true)).collect()
+    };
+
+    let library = load_library(app)?;
+    let disks = Disks::new_with_refreshed_list();
+    Ok(folders.into_iter()
+        .map(|(folder, configured)| {
+            let canonical_folder = folder.canonicalize().unwrap_or_else(|_| folder.clone());
+            let disk = find_disk_for_path(&disks, &folder);
+            let mut game_ids: Vec<u64> = library.values()
+                .filter(|installed_game| {
+                    let game_path = Path::new(&installed_game.pathInstallLocation);
+                    game_path.canonicalize().unwrap_or_else(|_| game_path.to_path_buf()).starts_with(&canonical_folder)
+                })
+                .map(|installed_game| installed_game.gameId)
+                .collect();
+            game_ids.sort();
+
+            LibraryFolder {
+                path: folder.to_string_lossy().into_owned(),
+                configured,
+                totalSpace: disk.map(|disk| disk.total_space()),
+                availableSpace: disk.map(|disk| disk.available_space()),
+                gameIds: game_ids,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+fn list_library_folders(app: tauri::AppHandle) -> Result<Vec<LibraryFolder>, String> {
+    describe_library_folders(&app)
+}
+
+#[tauri::command]
+fn add_library_folder(app: tauri::AppHandle, path: String) -> Result<Vec<LibraryFolder>, String> {
+    fs::create_dir_all(&path).map_err(|e| format!("Failed to create directory: {}: {}", path, e))?;
+    // Le chemin est enregistré tel qu'affiché et réutilisé dans pathInstallLocation et les raccourcis
+    let folder = Path::new(&path).canonicalize()
+        .map(strip_verbatim_prefix)
+        .map_err(|e| format!("Failed to resolve library folder: {}", e))?
+        .to_string_lossy()
+        .into_owned();
+
+    let mut library_folders = load_library_folders(&app)?;
+    if !library_folders.contains(&folder) {
+        library_folders.push(folder);
+        save_library_folders(&app, &library_folders)?;
+    }
+    describe_library_folders(&app)
+}
+
+// Les jeux installés dans le dossier restent dans la bibliothèque
+#[tauri::command]
+fn remove_library_folder(app: tauri::AppHandle, path: String) -> Result<Vec<LibraryFolder>, String> {
+    let mut library_folders = load_library_folders(&app)?;
+    forget_library_folder(&mut library_folders, &path);
+    save_library_folders(&app, &library_folders)?;
+    describe_library_folders(&app)
+}
+
+// Retire le dossier enregistré par add_library_folder (chemin résolu), ou tel quel s'il n'existe plus
+fn forget_library_folder(library_folders: &mut Vec<String>, path: &str) {
+    let resolved_path = Path::new(path).canonicalize()
+        .map(|resolved_path| strip_verbatim_prefix(resolved_path).to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_string());
+    library_folders.retain(|folder| folder != path && folder != &resolved_path);
+}
+
+// Dossier conseillé pour une nouvelle installation : celui qui a le plus d'espace libre
+#[tauri::command]
+fn suggest_library_folder(app: tauri::AppHandle) -> Result<LibraryFolder, String> {
+    describe_library_folders(&app)?
+        .into_iter()
+        .max_by_key(|folder| folder.availableSpace.unwrap_or(0))
+        .ok_or_else(|| "No library folder available".to_string())
+}
+
+// Dossier de destination d'un déplacement : vide ou inexistant, et sans chevauchement avec le dossier actuel
+fn resolve_move_destination(game_directory: &Path, new_path: &str) -> Result<PathBuf, String> {
+    let destination = Path::new(new_path);
+    if destination.exists() && !is_empty_directory(destination) {
+        return Err(format!("Destination is not an empty directory: {}", new_path));
+    }
+
+    let parent = destination.parent().ok_or_else(|| format!("Invalid destination: {}", new_path))?;
+    let directory_name = destination.file_name().ok_or_else(|| format!("Invalid destination: {}", new_path))?;
+    fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}: {}", parent.display(), e))?;
+    let destination = parent.canonicalize()
+        .map(strip_verbatim_prefix)
+        .map_err(|e| format!("Failed to resolve destination: {}", e))?
+        .join(directory_name);
+
+    if destination.starts_with(game_directory) || game_directory.starts_with(&destination) {
+        return Err(format!("Destination overlaps the current installation: {}", destination.display()));
+    }
+    Ok(destination)
+}
+
+// Fichiers de dir correspondant aux chemins à conserver, sans suivre les liens symboliques
+fn collect_preserved_files(game_directory: &Path, dir: &Path, preserved_paths: &[String], preserved_files: &mut Vec<String>) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read directory: {}: {}", dir.display(), e))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if entry.file_type().map_err(|e| e.to_string())?.is_dir() {
+            collect_preserved_files(game_directory, &path, preserved_paths, preserved_files)?;
+        } else {
+            let relative_path = relative_display_path(game_directory, &path);
+            if is_preserved_path(&relative_path, preserved_paths) {
+                preserved_files.push(relative_path);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Supprime les fichiers copiés dans destination et le dossier s'il est vide
+fn rollback_copied_files(destination: &Path, copied_files: &[PathBuf]) {
+    for copied_file in copied_files {
+        if let Err(e) = fs::remove_file(copied_file) {
+            eprintln!("Failed to remove copied file: {}: {}", copied_file.display(), e);
+        }
+    }
+    if let Err(e) = prune_empty_directories(destination) {
+        eprintln!("Failed to clean up destination: {}", e);
+    }
+    if is_empty_directory(destination) {
+        let _ = fs::remove_dir(destination);
+    }
+}
+
+// Copie un fichier et vérifie son hash si il est connu, la copie invalide est supprimée
+fn copy_game_file(source_path: &Path, target_path: &Path, expected_hash: Option<&str>) -> Result<u64, String> {
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}: {}", parent.display(), e))?;
+    }
+    let file_size = fs::copy(source_path, target_path)
+        .map_err(|e| format!("Failed to copy {}: {}", source_path.display(), e))?;
+
+    if let Some(expected_hash) = expected_hash {
+        let copied_hash = calculate_file_hash(target_path)?;
+        if copied_hash != expected_hash {
+            let _ = fs::remove_file(target_path);
+            return Err(format!("File hash mismatch for {}: expected {}, got {}", target_path.display(), expected_hash, copied_hash));
+        }
+    }
+    Ok(file_size)
+}
+
+// Fichiers à copier lors d'un déplacement : (chemin relatif, hash attendu), les données du joueur,
+// la version précédente et une mise à jour en préparation ne sont pas dans le manifeste
+fn game_files_to_copy<'a>(game_directory: &Path, manifest: &'a GameManifestLocal) -> Result<Vec<(String, Option<&'a str>)>, String> {
+    let mut unlisted_files = Vec::new();
+    collect_preserved_files(game_directory, game_directory, &manifest.preservedPaths, &mut unlisted_files)?;
+    for launcher_directory in [STAGING_DIR_NAME, PREVIOUS_VERSION_DIR_NAME] {
+        let launcher_directory = game_directory.join(launcher_directory);
+        if launcher_directory.is_dir() {
+            collect_directory_files(game_directory, &launcher_directory, &mut unlisted_files)?;
+        }
+    }
+
+    let mut files_to_copy: Vec<(String, Option<&str>)> = manifest.files.iter()
+        .filter(|file| is_safe_relative_path(&file.name))
+        .map(|file| (file.name.clone(), Some(file.hash.as_str())))
+        .collect();
+    for unlisted_file in unlisted_files {
+        if !files_to_copy.iter().any(|(name, _)| Path::new(name) == Path::new(&unlisted_file)) {
+            files_to_copy.push((unlisted_file, None));
+        }
+    }
+    Ok(files_to_copy)
+}
+
+// Liste tous les fichiers de dir, relatifs au dossier du jeu
+fn collect_directory_files(game_directory: &Path, dir: &Path, files: &mut Vec<String>) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read directory: {}: {}", dir.display(), e))? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if entry.file_type().map_err(|e| e.to_string())?.is_dir() {
+            collect_directory_files(game_directory, &path, files)?;
+        } else {
+            files.push(relative_display_path(game_directory, &path));
+        }
+    }
+    Ok(())
+}
+
+// Copie les fichiers du manifeste, les données du joueur, la version précédente et une mise à jour
+// en préparation vers destination et vérifie les hash connus, renvoie les fichiers relatifs copiés
+// (les fichiers déjà copiés sont supprimés en cas d'erreur)
+fn copy_game_files(app: &tauri::AppHandle, game_directory: &Path, destination: &Path, manifest: &GameManifestLocal) -> Result<Vec<String>, String> {
+    let files_to_copy = game_files_to_copy(game_directory, manifest)?;
+
+    let total_size: u64 = files_to_copy.iter()
+        .filter_map(|(name, _)| fs::metadata(game_directory.join(name)).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+    let mut copied_size = 0;
+    let mut copied_files = Vec::new();
+
+    for (name, expected_hash) in &files_to_copy {
+        let source_path = game_directory.join(name);
+        let target_path = destination.join(name);
+        match copy_game_file(&source_path, &target_path, *expected_hash) {
+            Ok(file_size) => {
+                copied_size += file_size;
+                copied_files.push(target_path);
+            }
+            Err(e) => {
+                rollback_copied_files(destination, &copied_files);
+                return Err(e);
+            }
+        }
+
+        if let Err(e) = app.emit("move-game-progress", Some(json!({
+            "gameId": manifest.gameId,
+            "fileName": name,
+            "copiedSize": copied_size,
+            "totalSize": total_size,
+        }))) {
+            eprintln!("Failed to emit move progress event: {}", e);
+        }
+    }
+
+    Ok(files_to_copy.into_iter().map(|(name, _)| name).collect())
+}
+
+// moveGameInstallation : déplace le jeu vers new_path (renommage sur le même disque, sinon copie
+// vérifiée des fichiers du manifeste, des données du joueur et des dossiers du launcher, annulée en cas d'erreur)
+#[tauri::command]
+async fn move_game_installation(app: tauri::AppHandle, game_id: u64, new_path: String) -> Result<GameManifestLocal, String> {
+    if RUNNING_GAMES.lock().unwrap().contains_key(&game_id) {
+        return Err(format!("Game {} is running, close it before moving it", game_id));
+    }
+    if is_download_active(game_id) {
+        return Err(format!("Game {} is being downloaded, wait for the download to finish before moving it", game_id));
+    }
+
+    let current_path = installed_game_directory(&app, game_id)?;
+    let mut manifest = read_local_manifest(&current_path)?
+        .ok_or_else(|| format!("manifest_local.json not found in {}", current_path))?;
+    let game_directory = Path::new(&current_path).canonicalize()
+        .map(strip_verbatim_prefix)
+        .map_err(|e| format!("Failed to resolve game directory: {}", e))?;
+    let destination = resolve_move_destination(&game_directory, &new_path)?;
+    let destination_location = destination.to_string_lossy().into_owned();
+    manifest.pathInstallLocation = destination_location.clone();
+
+    // Même disque : un simple renommage déplace tout le dossier, fichiers de l'utilisateur compris
+    if destination.exists() {
+        let _ = fs::remove_dir(&destination);
+    }
+    if fs::rename(&game_directory, &destination).is_ok() {
+        if let Err(e) = save_manifest(&destination_location, &manifest) {
+            let _ = fs::rename(&destination, &game_directory);
+            return Err(e);
+        }
+    } else {
+        println!("Rename failed, copying the game files to {}", destination.display());
+        // Copie et vérification des hash hors du runtime async (fichiers de plusieurs Go)
+        let (copy_app, copy_source, copy_destination, copy_manifest) = (app.clone(), game_directory.clone(), destination.clone(), manifest.clone());
+        let copied_files = tokio::task::spawn_blocking(move || copy_game_files(&copy_app, &copy_source, &copy_destination, &copy_manifest))
+            .await
+            .map_err(|e| format!("Failed to copy the game files: {}", e))??;
+        if let Err(e) = save_manifest(&destination_location, &manifest) {
+            let copied_files: Vec<PathBuf> = copied_files.iter().map(|name| destination.join(name)).collect();
+            rollback_copied_files(&destination, &copied_files);
+            return Err(e);
+        }
+
+        // La copie est vérifiée : suppression des fichiers copiés dans l'ancien dossier
+        let mut report = UninstallReport::default();
+        for name in copied_files.iter().map(String::as_str).chain(["manifest_local.json"]) {
+            remove_owned_file(&game_directory, &game_directory.join(name), &mut report);
+        }
+        prune_empty_directories(&game_directory)?;
+        if is_empty_directory(&game_directory) {
+            let _ = fs::remove_dir(&game_directory);
+        }
+    }
+
+    register_installed_game(&app, &manifest)?;
+    if let Err(e) = update_game_installed(&app, &manifest) {
+        eprintln!("Failed to update gamesInstalled.json: {}", e);
+    }
+
+    // Recréer les raccourcis vers le nouvel emplacement avec les mêmes options
+    if let Some(game_shortcuts) = load_all_shortcuts(&app)?.get(&game_id).cloned() {
+        if let Err(e) = apply_game_shortcuts(&app, &destination, game_shortcuts.throughLauncher, game_shortcuts.options) {
+            eprintln!("Failed to update shortcuts: {}", e);
+        }
+    }
+
+    app.emit("move-game-complete", Some(json!({
+        "gameId": game_id,
+        "pathInstallLocation": destination_location,
+    })))
+        .map_err(|e| format!("Failed to emit move complete event: {}", e))?;
+
+    Ok(manifest)
+}
+
+// Résultat d'un import : fichiers déjà présents et valides, et ceux qui restent à télécharger
+// (le jeu n'est ajouté à la bibliothèque que s'il ne reste rien à télécharger)
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct ImportReport {
+    matchedFiles: Vec<String>,
+    filesToDownload: Vec<FileDetails>,
+    sizeToDownload: u64,
+}
+
+// Sépare les fichiers du manifeste déjà présents et valides dans game_directory de ceux à télécharger
+fn match_existing_files(
+    app: &tauri::AppHandle,
+    game_id: u64,
+    game_directory: &Path,
+    files: &[FileDetails],
+) -> Result<(Vec<FileDetails>, Vec<FileDetails>), String> {
+    let mut matched_files = Vec::new();
+    let mut files_to_download = Vec::new();
+    for (index, file) in files.iter().enumerate() {
+        let file_path = game_directory.join(&file.name);
+
+        // La taille est comparée avant de calculer le hash, pour ne pas lire les fichiers différents
+        let is_matching = is_safe_relative_path(&file.name)
+            && fs::metadata(&file_path).is_ok_and(|metadata| metadata.is_file() && metadata.len() == file.size)
+            && calculate_file_hash(&file_path).is_ok_and(|hash| hash == file.hash);
+
+        if is_matching {
+            // Les droits sont perdus par une copie depuis certains systèmes de fichiers (FAT, NTFS)
+            apply_unix_mode(&file_path, file.unix_mode())?;
+            matched_files.push(file.clone());
+        } else {
+            files_to_download.push(file.clone());
+        }
+
+        if let Err(e) = app.emit("import-game-progress", Some(json!({
+            "gameId": game_id,
+            "checkedFiles": index + 1,
+            "totalFiles": files.len(),
+        }))) {
+            eprintln!("Failed to emit import progress event: {}", e);
+        }
+    }
+    Ok((matched_files, files_to_download))
+}
+
+// importGameInstallation : adopte un dossier existant (copie depuis une clé USB, réinstallation de l'OS...)
+// en vérifiant les fichiers présents avec le manifeste distant, puis écrit un nouveau manifest_local.json
+#[tauri::command]
+async fn import_game_installation(
+    app: tauri::AppHandle,
+    path: String,
+    game_id: u64,
+    game_title: String,
+    game_manifest_remote: GameManifestRemote,
+) -> Result<ImportReport, String> {
+    let game_directory = Path::new(&path);
+    if !game_directory.is_dir() {
+        return Err(format!("Game directory does not exist or is not a directory: {}", path));
+    }
+
+    if let Some(existing_manifest) = read_local_manifest(&path)? {
+        if existing_manifest.gameId != game_id {
+            return Err(format!("{} already contains the game {}", path, existing_manifest.gameId));
+        }
+    }
+
+    // Les fichiers de tous les composants et de toutes les langues sont comparés : ceux gardés sont ceux présents sur le disque
+    // Calcul des hash hors du runtime async (fichiers de plusieurs Go)
+    let (match_app, match_directory, remote_files) = (app.clone(), game_directory.to_path_buf(), game_manifest_remote.files.clone());
+    let (matched_files, files_to_download) = tokio::task::spawn_blocking(move || {
+        match_existing_files(&match_app, game_id, &match_directory, &remote_files)
+    })
+        .await
+        .map_err(|e| format!("Failed to check the game files: {}", e))??;
+
+    let selected_components = game_manifest_remote.detect_components(&matched_files)?;
+    let game_manifest_remote = game_manifest_remote.for_components(&selected_components);
+    let selected_languages = game_manifest_remote.detect_languages(&matched_files)?;
+    let game_manifest_remote = game_manifest_remote.for_languages(selected_languages.as_deref());
+    let files_to_download: Vec<FileDetails> = files_to_download.into_iter()
+        .filter(|file| file.is_in_components(&selected_components) && file.is_in_languages(selected_languages.as_deref()))
+        .collect();
+
+    let mut manifest = GameManifestLocal {
+        pathInstallLocation: path.clone(),
+        gameId: game_id,
+        gameTitle: game_title,
+        gameBinarySize: game_manifest_remote.files.iter().map(|file| file.size).sum(),
+        version: game_manifest_remote.version.clone(),
+        files: matched_files,
+        components: Some(selected_components),
+        languages: selected_languages,
+        ..Default::default()
+    };
+    apply_remote_manifest_settings(&mut manifest, &game_manifest_remote);
+    save_manifest(&path, &manifest)?;
+
+    // Import incomplet : le jeu sera ajouté à la bibliothèque à la fin du téléchargement des fichiers manquants
+    if files_to_download.is_empty() {
+        register_installed_game(&app, &manifest)?;
+    }
+
+    Ok(ImportReport {
+        matchedFiles: manifest.files.iter().map(|file| file.name.clone()).collect(),
+        sizeToDownload: files_to_download.iter().map(|file| file.size).sum(),
+        filesToDownload: files_to_download,
+    })
+}
+
+// Différence entre la version installée et la version cible d'un canal
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct ChannelDiff {
+    currentChannel: ReleaseChannel,
+    currentVersion: String,
+    targetVersion: String,
+    // Fichiers nouveaux, modifiés ou manquants sur le disque
+    filesToDownload: Vec<FileDetails>,
+    sizeToDownload: u64,
+    // Fichiers installés absents de la version cible (les données du joueur ne sont pas comptées)
+    obsoleteFiles: Vec<String>,
+    sizeToRemove: u64,
+}
+
+#[tauri::command]
+fn get_release_channel(app: tauri::AppHandle, game_id: u64) -> Result<ReleaseChannel, String> {
+    let path_install_location = installed_game_directory(&app, game_id)?;
+    Ok(read_local_manifest(&path_install_location)?.map(|manifest| manifest.channel).unwrap_or_default())
+}
+
+// Dossier du bucket contenant le manifest.json et les fichiers d'une version d'un canal
+#[tauri::command]
+fn get_release_channel_path(
+    path_filename: String,
+    channel: ReleaseChannel,
+    game_version: String,
+    os_architecture: String,
+) -> Result<String, String> {
+    channel.validate()?;
+    Ok(channel.build_path(&path_filename, &game_version, &os_architecture))
+}
+
+// Calcule ce que le passage de la version installée à la version cible (manifest.json du canal) téléchargera et supprimera
+#[tauri::command]
+fn compute_channel_diff(app: tauri::AppHandle, game_id: u64, game_manifest_remote: GameManifestRemote) -> Result<ChannelDiff, String> {
+    let path_install_location = installed_game_directory(&app, game_id)?;
+    let local_manifest = read_local_manifest(&path_install_location)?
+        .ok_or_else(|| format!("manifest_local.json not found in {}", path_install_location))?;
+    channel_diff(Path::new(&path_install_location), &local_manifest, &game_manifest_remote)
+}
+
+fn channel_diff(game_directory: &Path, local_manifest: &GameManifestLocal, game_manifest_remote: &GameManifestRemote) -> Result<ChannelDiff, String> {
+    let game_manifest_remote = game_manifest_remote
+        .for_components(&game_manifest_remote.installed_components(local_manifest)?)
+        .for_languages(game_manifest_remote.installed_languages(local_manifest)?.as_deref());
+
+    let files_to_download: Vec<FileDetails> = game_manifest_remote.files.iter()
+        .filter(|remote_file| {
+            let is_installed = local_manifest.files.iter()
+                .any(|local_file| local_file.name == remote_file.name && local_file.hash == remote_file.hash);
+            !is_installed || !game_directory.join(&remote_file.name).is_file()
+        })
+        .cloned()
+        .collect();
+
+    let obsolete_files: Vec<&FileDetails> = local_manifest.files.iter()
+        .filter(|local_file| !game_manifest_remote.files.iter().any(|remote_file| remote_file.name == local_file.name))
+        .filter(|local_file| !is_preserved_path(&local_file.name, &game_manifest_remote.preservedPaths))
+        .collect();
+
+    Ok(ChannelDiff {
+        currentChannel: local_manifest.channel.clone(),
+        currentVersion: local_manifest.version.clone(),
+        targetVersion: game_manifest_remote.version.clone(),
+        sizeToDownload: files_to_download.iter().map(|file| file.size).sum(),
+        filesToDownload: files_to_download,
+        sizeToRemove: obsolete_files.iter().map(|file| file.size).sum(),
+        obsoleteFiles: obsolete_files.into_iter().map(|file| file.name.clone()).collect(),
+    })
+}
+
+// Composant optionnel d'un jeu, avec sa taille et son état pour l'installation en cours
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct GameComponentInfo {
+    id: String,
+    name: String,
+    dependencies: Vec<String>,
+    default: bool,
+    size: u64,
+    installed: bool,
+}
+
+// Fichiers à télécharger pour installer un composant (à passer à download_and_update_game avec components)
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct ComponentChange {
+    components: Vec<String>,
+    filesToDownload: Vec<FileDetails>,
+    sizeToDownload: u64,
+}
+
+fn installed_game_manifest(app: &tauri::AppHandle, game_id: u64) -> Result<GameManifestLocal, String> {
+    let path_install_location = installed_game_directory(app, game_id)?;
+    read_local_manifest(&path_install_location)?
+        .ok_or_else(|| format!("manifest_local.json not found in {}", path_install_location))
+}
+
+#[tauri::command]
+fn list_game_components(app: tauri::AppHandle, game_id: u64, game_manifest_remote: GameManifestRemote) -> Result<Vec<GameComponentInfo>, String> {
+    let installed_components = match load_library(&app)?.get(&game_id) {
+        Some(installed_game) => read_local_manifest(&installed_game.pathInstallLocation)?
+            .and_then(|manifest| manifest.components)
+            .unwrap_or_default(),
+        None => vec![],
+    };
+
+    Ok(game_manifest_remote.components.iter().map(|component| GameComponentInfo {
+        id: component.id.clone(),
+        name: component.name.clone(),
+        dependencies: component.dependencies.clone(),
+        default: component.default,
+        size: game_manifest_remote.files.iter()
+            .filter(|file| file.component.as_ref() == Some(&component.id))
+            .map(|file| file.size)
+            .sum(),
+        installed: installed_components.contains(&component.id),
+    }).collect())
+}
+
+#[tauri::command]
+fn add_game_component(
+    app: tauri::AppHandle,
+    game_id: u64,
+    component_id: String,
+    game_manifest_remote: GameManifestRemote,
+) -> Result<ComponentChange, String> {
+    let local_manifest = installed_game_manifest(&app, game_id)?;
+    let game_directory = Path::new(&local_manifest.pathInstallLocation);
+
+    let mut requested = game_manifest_remote.installed_components(&local_manifest)?;
+    requested.push(component_id);
+    let components = game_manifest_remote.resolve_components(Some(&requested))?;
+
+    let languages = game_manifest_remote.installed_languages(&local_manifest)?;
+    let files_to_download: Vec<FileDetails> = game_manifest_remote
+        .for_components(&components)
+        .for_languages(languages.as_deref())
+        .files
+        .into_iter()
+        .filter(|remote_file| {
+            let is_installed = local_manifest.files.iter()
+                .any(|local_file| local_file.name == remote_file.name && local_file.hash == remote_file.hash);
+            !is_installed || !game_directory.join(&remote_file.name).is_file()
+        })
+        .collect();
+
+    Ok(ComponentChange {
+        components,
+        sizeToDownload: files_to_download.iter().map(|file| file.size).sum(),
+        filesToDownload: files_to_download,
+    })
+}
+
+// Supprime les fichiers d'un composant installé, refusé si un autre composant installé en dépend
+#[tauri::command]
+fn remove_game_component(
+    app: tauri::AppHandle,
+    game_id: u64,
+    component_id: String,
+    game_manifest_remote: GameManifestRemote,
+) -> Result<GameManifestLocal, String> {
+    if RUNNING_GAMES.lock().unwrap().contains_key(&game_id) {
+        return Err(format!("Game {} is running, close it before removing a component", game_id));
+    }
+
+    let mut local_manifest = installed_game_manifest(&app, game_id)?;
+    let installed_components = local_manifest.components.clone().unwrap_or_default();
+    if !installed_components.contains(&component_id) {
+        return Err(format!("Game component {} is not installed", component_id));
+    }
+
+    let remaining: Vec<String> = installed_components.into_iter().filter(|id| *id != component_id).collect();
+    for component in game_manifest_remote.components.iter().filter(|component| remaining.contains(&component.id)) {
+        if game_manifest_remote.resolve_components(Some(std::slice::from_ref(&component.id)))?.contains(&component_id) {
+            return Err(format!("Game component {} is required by {}", component_id, component.name));
+        }
+    }
 
-            if let Ok(executable) = find_executable_in_directory(&path) {
-                return Ok(executable);
-            }
-        } else if EXECUTABLE_EXTENSIONS.iter().any(|&ext| path.extension().map_or(false, |p_ext| p_ext == ext)) {
-            return Ok(path.to_string_lossy().into_owned());
-        } else if cfg!(target_os = "linux") && path.extension().is_none() {
-            // Vérifiez si le fichier sans extension est exécutable sous Linux
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                if let Ok(metadata) = fs::metadata(&path) {
-                    let mut permissions = metadata.permissions();
-                    permissions.set_mode(permissions.mode() | 0o111); // chmod +x
-                    if let Err(e) = fs::set_permissions(&path, permissions) {
-                        return Err(format!("Failed to set permissions: {}", e));
-                    }
-                    return Ok(path.to_string_lossy().into_owned());
-                }
+    let game_directory = PathBuf::from(&local_manifest.pathInstallLocation);
+    local_manifest.files.retain(|file| {
+        if file.component.as_ref() != Some(&component_id) {
+            return true;
+        }
+
+        let file_path = game_directory.join(&file.name);
+        if is_safe_relative_path(&file.name) && !is_preserved_path(&file.name, &local_manifest.preservedPaths) {
+            if let Err(e) = fs::remove_file(&file_path) {
+                eprintln!("Failed to remove file: {}: {}", file_path.display(), e);
             }
         }
+        false
+    });
+    prune_empty_directories(&game_directory)?;
+
+    local_manifest.components = Some(remaining);
+    local_manifest.gameBinarySize = local_manifest.files.iter().map(|file| file.size).sum();
+    save_manifest(&local_manifest.pathInstallLocation, &local_manifest)?;
+    register_installed_game(&app, &local_manifest)?;
+    if let Err(e) = update_game_installed(&app, &local_manifest) {
+        eprintln!("Failed to update gamesInstalled.json: {}", e);
     }
 
-    Err("No executable found in the directory".to_string())
+    Ok(local_manifest)
 }
 
-// createShortcut
+// Taille des fichiers propres à une langue
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct LanguageSize {
+    language: String,
+    size: u64,
+    // Langue installée par défaut
+    default: bool,
+}
+
+// Taille de chaque langue avant l'installation (seulement les fichiers des composants indiqués, ceux par défaut si None)
 #[tauri::command]
-fn create_shortcut(directory_path: String) -> Result<(), String> {
-    let system_os_info = get_system_os_info_current();
-    let os = &system_os_info.os;
-    let desktop_path = get_desktop_path().ok_or_else(|| {
-        let msg = "Failed to get desktop path";
-        println!("{}", msg); // Log error message
-        msg.to_string()
-    })?;
+fn get_language_sizes(game_manifest_remote: GameManifestRemote, components: Option<Vec<String>>) -> Result<Vec<LanguageSize>, String> {
+    let components = game_manifest_remote.resolve_components(components.as_deref())?;
+    let game_manifest_remote = game_manifest_remote.for_components(&components);
+
+    Ok(game_manifest_remote.available_languages().into_iter().map(|language| LanguageSize {
+        size: game_manifest_remote.files.iter()
+            .filter(|file| file.locales.iter().any(|locale| locale.eq_ignore_ascii_case(&language)))
+            .map(|file| file.size)
+            .sum(),
+        default: game_manifest_remote.defaultLanguage.as_ref().is_some_and(|default| default.eq_ignore_ascii_case(&language)),
+        language,
+    }).collect())
+}
 
-    let directory_path = Path::new(&directory_path);
+// Fichiers à télécharger après un changement de langues (à passer à download_and_update_game avec languages)
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct LanguageChange {
+    languages: Option<Vec<String>>,
+    filesToDownload: Vec<FileDetails>,
+    sizeToDownload: u64,
+}
 
-    // Pour macOS, nous devons trouver le bundle .app
-    #[cfg(target_os = "macos")]
-    let app_bundle_path = directory_path
-        .read_dir()
-        .map_err(|e| format!("Failed to read directory: {}", e))?
-        .filter_map(Result::ok)
-        .find(|entry| entry.path().extension().map_or(false, |ext| ext == "app"))
-        .map(|entry| entry.path())
-        .ok_or_else(|| "No .app bundle found in the directory".to_string())?;
+// Change les langues d'un jeu installé : les fichiers des langues retirées sont supprimés comme des fichiers obsolètes
+#[tauri::command]
+fn set_game_languages(
+    app: tauri::AppHandle,
+    game_id: u64,
+    languages: Vec<String>,
+    game_manifest_remote: GameManifestRemote,
+) -> Result<LanguageChange, String> {
+    if RUNNING_GAMES.lock().unwrap().contains_key(&game_id) {
+        return Err(format!("Game {} is running, close it before changing its languages", game_id));
+    }
 
-    #[cfg(any(target_os = "windows", target_os = "linux"))]
-    let executable_path = find_executable_in_directory(&directory_path).map_err(|e| {
-        println!("Failed to find executable: {}", e); // Log error message
-        e
-    })?;
+    let mut local_manifest = installed_game_manifest(&app, game_id)?;
+    let game_directory = PathBuf::from(&local_manifest.pathInstallLocation);
+    let languages = game_manifest_remote.resolve_languages(Some(&languages))?;
+    let components = game_manifest_remote.installed_components(&local_manifest)?;
+
+    // Seuls les fichiers des langues retirées sortent du manifeste, la version installée n'est pas modifiée
+    let mut kept_manifest = game_manifest_remote.clone();
+    kept_manifest.files = local_manifest.files.iter()
+        .filter(|file| file.is_in_languages(languages.as_deref()))
+        .cloned()
+        .collect();
+    remove_obsolete_files(&game_directory, &mut local_manifest, &kept_manifest, None)?;
+    prune_empty_directories(&game_directory)?;
+
+    let files_to_download: Vec<FileDetails> = game_manifest_remote
+        .for_components(&components)
+        .for_languages(languages.as_deref())
+        .files
+        .into_iter()
+        .filter(|remote_file| {
+            let is_installed = local_manifest.files.iter()
+                .any(|local_file| local_file.name == remote_file.name && local_file.hash == remote_file.hash);
+            !is_installed || !game_directory.join(&remote_file.name).is_file()
+        })
+        .collect();
+
+    local_manifest.languages = languages.clone();
+    local_manifest.gameBinarySize = local_manifest.files.iter().map(|file| file.size).sum();
+    save_manifest(&local_manifest.pathInstallLocation, &local_manifest)?;
+    register_installed_game(&app, &local_manifest)?;
+    if let Err(e) = update_game_installed(&app, &local_manifest) {
+        eprintln!("Failed to update gamesInstalled.json: {}", e);
+    }
 
-    #[cfg(any(target_os = "windows", target_os = "linux"))]
-    let exe_name = Path::new(&executable_path)
-        .file_stem()
-        .ok_or("Failed to get executable name")?
-        .to_str()
-        .ok_or("Failed to convert executable name to str")?;
+    Ok(LanguageChange {
+        languages,
+        sizeToDownload: files_to_download.iter().map(|file| file.size).sum(),
+        filesToDownload: files_to_download,
+    })
+}
 
-    match os.as_str() {
-        "Windows" => {
-            #[cfg(target_os = "windows")]
-            {
-                // Raccourci LNK for Windows
-                let shortcut_path = Path::new(&desktop_path).join(format!("{}.lnk", exe_name));
-                let sl = mslnk::ShellLink::new(&executable_path).map_err(|e| format!("Failed to create ShellLink: {}", e))?;
-                sl.create_lnk(shortcut_path.to_string_lossy().into_owned()).map_err(|e| format!("Failed to create lnk: {}", e))?;
-            }
+// Version précédente gardée pour un jeu
+#[derive(Debug, Clone, serde::Serialize)]
+#[allow(non_snake_case)]
+struct PreviousVersionInfo {
+    version: String,
+    // Taille des fichiers gardés
+    size: u64,
+}
+
+fn installed_game_directory(app: &tauri::AppHandle, game_id: u64) -> Result<String, String> {
+    load_library(app)?
+        .remove(&game_id)
+        .map(|installed_game| installed_game.pathInstallLocation)
+        .ok_or_else(|| format!("Game {} is not installed", game_id))
+}
+
+#[tauri::command]
+fn get_previous_version(app: tauri::AppHandle, game_id: u64) -> Result<Option<PreviousVersionInfo>, String> {
+    let previous_directory = Path::new(&installed_game_directory(&app, game_id)?).join(PREVIOUS_VERSION_DIR_NAME);
+    Ok(read_local_manifest(&previous_directory.to_string_lossy())?.map(|previous_manifest| PreviousVersionInfo {
+        version: previous_manifest.version,
+        size: directory_size(&previous_directory.join("files")),
+    }))
+}
+
+// rollbackGame : restaure la version précédente gardée par la dernière mise à jour et son manifeste
+#[tauri::command]
+async fn rollback_game(app: tauri::AppHandle, game_id: u64) -> Result<GameManifestLocal, String> {
+    if RUNNING_GAMES.lock().unwrap().contains_key(&game_id) {
+        return Err(format!("Game {} is running, close it before rolling back", game_id));
+    }
+
+    let path_install_location = installed_game_directory(&app, game_id)?;
+    let game_directory = Path::new(&path_install_location);
+    recover_staged_update(game_directory)?;
+
+    let current_manifest = read_local_manifest(&path_install_location)?
+        .ok_or_else(|| format!("manifest_local.json not found in {}", path_install_location))?;
+    let previous_directory = game_directory.join(PREVIOUS_VERSION_DIR_NAME);
+    let previous_files_directory = previous_directory.join("files");
+    let mut previous_manifest = read_local_manifest(&previous_directory.to_string_lossy())?
+        .ok_or_else(|| format!("No previous version kept for game {}", game_id))?;
+
+    // Chaque fichier de la version précédente doit être gardé ou inchangé dans la version actuelle
+    for file in &previous_manifest.files {
+        let is_kept = previous_files_directory.join(&file.name).is_file();
+        let is_unchanged = current_manifest.files.iter().any(|current_file| current_file.name == file.name && current_file.hash == file.hash);
+        if !is_kept && !is_unchanged {
+            return Err(format!("Previous version is incomplete, {} is missing", file.name));
         }
-        "macOS" => {
-            #[cfg(target_os = "macos")]
-            {
-                // Raccourci ALIAS for macOS
-                let alias_name = format!("{}.alias", app_bundle_path.file_stem().unwrap().to_str().unwrap());
-                let alias_path = Path::new(&desktop_path).join(&alias_name);
-                let apple_script = format!(
-                    "tell application \"Finder\" to make alias file to POSIX file \"{}\" at POSIX file \"{}\"",
-                    app_bundle_path.to_str().unwrap(), // Utilise le chemin du bundle .app
-                    alias_path.parent().unwrap().to_str().unwrap()
-                );
-
-                // Execute AppleScript
-                Command::new("osascript")
-                    .arg("-e")
-                    .arg(&apple_script)
-                    .output()
-                    .map_err(|e| format!("Failed to create alias: {}", e))?;
-            }
+    }
+
+    for file in previous_manifest.files.iter().filter(|file| is_safe_relative_path(&file.name)) {
+        let kept_path = previous_files_directory.join(&file.name);
+        if kept_path.is_file() {
+            move_file(&kept_path, &game_directory.join(&file.name))?;
         }
-        "Linux" => {
-            #[cfg(target_os = "linux")]
-            {
-                // Définir les chemins de l'icône et des fichiers .desktop
-                let icon_name = format!("{}.png", exe_name);
-                let icon_source_path = directory_path.join(&icon_name);
-                let icon_dest_path = dirs::home_dir().unwrap().join(".icons").join(&icon_name);
-
-                // Créer le répertoire d'icônes s'il n'existe pas
-                if !icon_dest_path.parent().unwrap().exists() {
-                    fs::create_dir_all(icon_dest_path.parent().unwrap())
-                        .map_err(|e| format!("Failed to create icons directory: {}", e))?;
-                }
+    }
 
-                // Copier l'icône vers le répertoire des icônes
-                fs::copy(&icon_source_path, &icon_dest_path)
-                    .map_err(|e| format!("Failed to copy icon: {}", e))?;
-
-                // Créer le fichier .desktop
-                let desktop_entry = format!(
-                    "[Desktop Entry]\n\
-                    Name={}\n\
-                    Exec=\"{}\"\n\
-                    Icon={}\n\
-                    Type=Application\n\
-                    Categories=Game;\n\
-                    Terminal=false\n",
-                    exe_name,
-                    executable_path,
-                    icon_name
-                );
-
-                let applications_path = dirs::data_dir().unwrap().join("applications");
-                if !applications_path.exists() {
-                    fs::create_dir_all(&applications_path)
-                        .map_err(|e| format!("Failed to create applications directory: {}", e))?;
-                }
-                let desktop_file_path = Path::new(&desktop_path).join(format!("{}.desktop", exe_name));
-                let applications_file_path = applications_path.join(format!("{}.desktop", exe_name));
-
-                // Écrire le fichier .desktop et définir les permissions pour le bureau
-                fs::write(&desktop_file_path, &desktop_entry)
-                    .map_err(|e| format!("Failed to create .desktop file on desktop: {}", e))?;
-                Command::new("chmod")
-                    .arg("+x")
-                    .arg(&desktop_file_path)
-                    .output()
-                    .map_err(|e| format!("Failed to set .desktop file as executable on desktop: {}", e))?;
-
-                // Activer "Allow Launching"
-                Command::new("gio")
-                    .arg("set")
-                    .arg(&desktop_file_path)
-                    .arg("metadata::trusted")
-                    .arg("true")
-                    .output()
-                    .map_err(|e| format!("Failed to set metadata::trusted on .desktop file: {}", e))?;
-
-                // Écrire le fichier .desktop et définir les permissions pour applications
-                fs::write(&applications_file_path, &desktop_entry)
-                    .map_err(|e| format!("Failed to create .desktop file in applications: {}", e))?;
-                Command::new("chmod")
-                    .arg("+x")
-                    .arg(&applications_file_path)
-                    .output()
-                    .map_err(|e| format!("Failed to set .desktop file as executable in applications: {}", e))?;
+    // Fichiers ajoutés par la mise à jour
+    for file in &current_manifest.files {
+        let is_previous_file = previous_manifest.files.iter().any(|previous_file| previous_file.name == file.name);
+        if !is_previous_file && is_safe_relative_path(&file.name) && !is_preserved_path(&file.name, &previous_manifest.preservedPaths) {
+            let file_path = game_directory.join(&file.name);
+            if let Err(e) = fs::remove_file(&file_path) {
+                eprintln!("Failed to remove file: {}: {}", file_path.display(), e);
             }
         }
-        _ => return Err("Unsupported OS".to_string()),
     }
 
-    Ok(())
+    previous_manifest.pathInstallLocation = path_install_location.clone();
+    save_manifest(&path_install_location, &previous_manifest)?;
+    reset_previous_version(game_directory, None)?;
+    prune_empty_directories(game_directory)?;
+
+    register_installed_game(&app, &previous_manifest)?;
+    if let Err(e) = update_game_installed(&app, &previous_manifest) {
+        eprintln!("Failed to update gamesInstalled.json: {}", e);
+    }
+
+    Ok(previous_manifest)
 }
 
-fn get_desktop_path() -> Option<PathBuf> {
-    dirs::home_dir().map(|path| path.join("Desktop"))
+// Demande transmise au launcher par une URL crzgames:// ou des arguments en ligne de commande
+// (ex: "crzgames://launch/42", "crzgames://install/42", "--launch 42", "--install=42")
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "action", rename_all = "camelCase", rename_all_fields = "camelCase")]
+enum DeepLinkRequest {
+    Launch { game_id: u64 },
+    Install { game_id: u64 },
 }
 
-#[tauri::command]
-async fn launch_game(file_location_download: String) -> Result<(), String> {
-    // Créer un canal pour transmettre les erreurs
-    let (tx, mut rx) = mpsc::channel(1);
+const DEEP_LINK_SCHEME: &str = "crzgames://";
 
-    thread::spawn(move || {
-        // Vérifiez si le répertoire de jeu existe
-        let game_dir = std::path::Path::new(&file_location_download);
-        if !game_dir.exists() {
-            let _ = tx.blocking_send(Err(format!("Game directory does not exist: {:?}", game_dir)));
-            return;
-        }
+lazy_static! {
+    // Demande reçue au démarrage du launcher, avant que le frontend n'écoute les events
+    static ref PENDING_DEEP_LINK: Mutex<Option<DeepLinkRequest>> = Mutex::new(None);
+}
 
-        // Trouver l'exécutable dans le répertoire du jeu
-        let executable_path = match find_executable_in_directory(&game_dir) {
-            Ok(path) => path,
-            Err(e) => {
-                let _ = tx.blocking_send(Err(format!("Error finding executable: {}", e)));
-                return;
-            }
-        };
-        let game_path = std::path::Path::new(&executable_path);
+fn deep_link_request_from(action: &str, game_id: &str) -> Option<DeepLinkRequest> {
+    let game_id = game_id.trim().parse::<u64>().ok()?;
+    match action.to_lowercase().as_str() {
+        "launch" => Some(DeepLinkRequest::Launch { game_id }),
+        "install" => Some(DeepLinkRequest::Install { game_id }),
+        _ => None,
+    }
+}
 
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut permissions = match std::fs::metadata(&game_path) {
-                Ok(metadata) => metadata.permissions(),
-                Err(e) => {
-                    let _ = tx.blocking_send(Err(format!("Failed to get metadata: {}", e)));
-                    return;
-                }
-            };
-            permissions.set_mode(permissions.mode() | 0o111); // chmod +x
-            if let Err(e) = std::fs::set_permissions(&game_path, permissions) {
-                let _ = tx.blocking_send(Err(format!("Failed to set permissions: {}", e)));
-                return;
-            }
+fn parse_deep_link_url(url: &str) -> Option<DeepLinkRequest> {
+    let scheme = url.get(..DEEP_LINK_SCHEME.len())?;
+    if !scheme.eq_ignore_ascii_case(DEEP_LINK_SCHEME) {
+        return None;
+    }
+
+    // Ignorer la query string et le fragment
+    let path = url[DEEP_LINK_SCHEME.len()..].split(['?', '#']).next().unwrap_or_default();
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let request = deep_link_request_from(segments.next()?, segments.next()?)?;
+    if segments.next().is_some() {
+        return None;
+    }
+    Some(request)
+}
+
+// Analyse les arguments du launcher (le premier argument est le chemin de l'exécutable)
+fn parse_deep_link_args(args: &[String]) -> Option<DeepLinkRequest> {
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some(request) = parse_deep_link_url(arg) {
+            return Some(request);
         }
 
-        // Utiliser std::process::Command pour lancer le jeu et capturer les erreurs
-        use std::process::Command;
-        let output = match Command::new(&game_path)
-            .current_dir(&game_dir)
-            .output()
-        {
-            Ok(output) => output,
-            Err(e) => {
-                let _ = tx.blocking_send(Err(format!("Failed to launch game: {}", e)));
-                return;
+        for action in ["launch", "install"] {
+            let flag = format!("--{}", action);
+            if let Some(game_id) = arg.strip_prefix(&format!("{}=", flag)) {
+                return deep_link_request_from(action, game_id);
+            }
+            if *arg == flag {
+                return deep_link_request_from(action, args.next()?);
             }
-        };
-
-        // Vérifiez si le processus a renvoyé une erreur
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let _ = tx.blocking_send(Err(format!("Failed to launch game: {}", stderr)));
-            return;
         }
+    }
 
-        let _ = tx.blocking_send(Ok(()));
-    });
+    None
+}
 
-    // Recevoir et traiter le résultat sans bloquer le thread principal
-    if let Some(result) = rx.recv().await {
-        return result;
+// Transmet une demande reçue pendant que le launcher tourne au frontend
+fn handle_deep_link_request(app: &tauri::AppHandle, request: Option<DeepLinkRequest>) {
+    show_main_window(app);
+
+    if let Some(request) = request {
+        if let Err(e) = app.emit("deep-link-request", &request) {
+            eprintln!("Failed to emit deep link request event: {}", e);
+        }
     }
+}
 
-    Ok(())
+// Arguments d'une seconde instance du launcher (Windows et Linux passent l'URL crzgames:// en argument)
+fn handle_deep_link_args(app: &tauri::AppHandle, args: &[String]) {
+    handle_deep_link_request(app, parse_deep_link_args(args));
 }
 
-#[tauri::command]
-async fn uninstall_game(path_install_location: String) -> Result<(), String> {
-    let game_directory = Path::new(&path_install_location);
+fn parse_deep_link_urls(urls: &[url::Url]) -> Option<DeepLinkRequest> {
+    urls.iter().find_map(|url| parse_deep_link_url(url.as_str()))
+}
 
-    // Vérifier si le répertoire existe
-    if game_directory.exists() && game_directory.is_dir() {
-        // Supprimer le répertoire et son contenu
-        remove_dir_all(game_directory).map_err(|e| format!("Failed to remove game directory: {}", e))?;
-        Ok(())
-    } else {
-        Err(format!("Game directory does not exist or is not a directory: {}", path_install_location))
-    }
+#[tauri::command]
+fn take_pending_deep_link() -> Option<DeepLinkRequest> {
+    PENDING_DEEP_LINK.lock().unwrap().take()
 }
 
 // Commande Tauri pour vérifier la connexion internet
@@ -880,7 +3946,8 @@ pub fn run() {
             MacosLauncher::LaunchAgent,
             None,
         ))
-        .plugin(tauri_plugin_single_instance::init(|_app, _args, _cwd| {}))
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| handle_deep_link_args(app, &args)))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -898,6 +3965,26 @@ pub fn run() {
                 .build(),
         )
         .setup(|app| {
+            // Schéma crzgames:// déclaré dans tauri.conf.json et enregistré par les installateurs, enregistré
+            // aussi au démarrage pour une AppImage non intégrée au système et en mode debug sous Windows
+            #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+            if let Err(e) = app.deep_link().register_all() {
+                eprintln!("Failed to register crzgames:// URL scheme: {}", e);
+            }
+
+            // Demande éventuelle reçue au démarrage (URL crzgames:// ou arguments), récupérée par le frontend.
+            // Sous macOS l'URL n'est pas dans les arguments : elle est transmise par un Apple event
+            let startup_urls = app.deep_link().get_current().ok().flatten().unwrap_or_default();
+            *PENDING_DEEP_LINK.lock().unwrap() = parse_deep_link_args(&env::args().collect::<Vec<String>>())
+                .or_else(|| parse_deep_link_urls(&startup_urls));
+
+            // URL ouvertes pendant que le launcher tourne (Apple events sous macOS, les autres systèmes
+            // passent par le plugin single-instance)
+            let deep_link_app = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                handle_deep_link_request(&deep_link_app, parse_deep_link_urls(&event.urls()));
+            });
+
             // Ouvre DevTools uniquement en mode debug (This is true for `tauri dev` and `tauri build --debug`)
             #[cfg(debug_assertions)]
             {
@@ -910,23 +3997,23 @@ pub fn run() {
             app.handle()
                 .plugin(tauri_plugin_updater::Builder::new().build())?;
 
-            // Définir le menu de la barre d'état
-            let show = MenuItemBuilder::new("Open CrzGames").id("show").build(app).unwrap();
-            let hide = MenuItemBuilder::new("Hide CrzGames").id("hide").build(app).unwrap();
-            let quit = MenuItemBuilder::new("Quit CrzGames").id("quit").build(app).unwrap();
-
             // Créer le menu de la barre d'état
-            let menu = MenuBuilder::new(app)
-                .items(&[&show, &hide, &quit])
-                .build()
-                .unwrap();
+            let menu = build_tray_menu(app.handle())?;
 
             // Créer l'icône de la barre d'état avec le menu défini ci-dessus et les events associés
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id("main")
                 .icon(Image::from_bytes(include_bytes!("../icons/icon.png"))?)
+                .tooltip("CrzGames")
                 .menu(&menu)
                 .on_menu_event(|app, event| match event.id().as_ref() {
                     "quit" => app.exit(0),
+                    id if id.starts_with("stop-game-") => {
+                        if let Ok(game_id) = id.trim_start_matches("stop-game-").parse::<u64>() {
+                            if let Err(e) = stop_game(app.clone(), game_id) {
+                                eprintln!("{}", e);
+                            }
+                        }
+                    }
                     "hide" => {
                         let window = app.get_webview_window("main").unwrap();
                         window.hide().unwrap();
@@ -958,6 +4045,7 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             get_launcher_path_directory,
             check_disk_space,
+            get_disk_space,
             download_and_update_game,
             launch_game,
             create_shortcut,
@@ -967,7 +4055,770 @@ pub fn run() {
             pause_download,
             cancel_download,
             check_internet_connection,
+            get_launch_options,
+            set_launch_options,
+            reset_launch_options,
+            verify_game_before_launch,
+            clean_up_game,
+            get_launcher_settings,
+            set_launcher_settings,
+            stop_game,
+            take_pending_deep_link,
+            list_shortcuts,
+            remove_shortcuts,
+            repair_shortcuts,
+            get_shortcut_options,
+            move_game_installation,
+            import_game_installation,
+            list_installed_games,
+            rescan_library,
+            list_library_folders,
+            add_library_folder,
+            remove_library_folder,
+            suggest_library_folder,
+            get_previous_version,
+            rollback_game,
+            get_release_channel,
+            get_release_channel_path,
+            compute_channel_diff,
+            list_game_components,
+            add_game_component,
+            remove_game_component,
+            get_language_sizes,
+            set_game_languages,
+            set_shortcut_options,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(path: &Path, content: &[u8]) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn native_executable_requires_header_and_permissions() {
+        let dir = tempfile::tempdir().unwrap();
+        let elf = dir.path().join("game");
+        let script = dir.path().join("start");
+        let license = dir.path().join("LICENSE");
+        write_file(&elf, b"\x7fELF\x02\x01\x01\x00");
+        write_file(&script, b"#!/bin/sh\nexec ./game\n");
+        write_file(&license, b"MIT License\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&elf, fs::Permissions::from_mode(0o755)).unwrap();
+            fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+            fs::set_permissions(&license, fs::Permissions::from_mode(0o755)).unwrap();
+            assert!(!is_native_executable_file(&script));
+        }
+
+        assert!(is_native_executable_file(&elf));
+        assert!(!is_native_executable_file(&license));
+        assert!(!is_native_executable_file(&dir.path().join("missing")));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn undeclared_elf_binary_is_not_made_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let elf = dir.path().join("game");
+        write_file(&elf, b"\x7fELF\x02\x01\x01\x00");
+        fs::set_permissions(&elf, fs::Permissions::from_mode(0o644)).unwrap();
+
+        assert!(find_executable_in_directory(dir.path()).is_err());
+        assert!(ensure_executable_permissions(dir.path(), &elf).is_err());
+        assert_eq!(fs::metadata(&elf).unwrap().permissions().mode() & 0o111, 0);
+
+        // Déclaré par le manifest : les droits sont ajoutés
+        let manifest = GameManifestLocal { executable: Some("game".to_string()), ..Default::default() };
+        save_manifest(&dir.path().to_string_lossy(), &manifest).unwrap();
+        ensure_executable_permissions(dir.path(), &elf).unwrap();
+        assert_ne!(fs::metadata(&elf).unwrap().permissions().mode() & 0o111, 0);
+    }
+
+    fn remote_manifest(value: serde_json::Value) -> GameManifestRemote {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn declared_executable_prefers_exact_architecture() {
+        let system_os_info = get_system_os_info_current();
+        let manifest = remote_manifest(json!({
+            "version": "1.0.0",
+            "files": [],
+            "executables": [
+                { "os": "OtherOS", "path": "other/Game" },
+                { "os": system_os_info.os, "path": "../outside/Game" },
+                { "os": system_os_info.os, "path": "bin/Game" },
+                { "os": system_os_info.os.to_uppercase(), "architecture": system_os_info.architecture, "path": "bin/native/Game" },
+            ],
+        }));
+        assert_eq!(manifest.executable_for_current_platform().as_deref(), Some("bin/native/Game"));
+
+        let manifest = remote_manifest(json!({
+            "version": "1.0.0",
+            "files": [],
+            "executables": [
+                { "os": system_os_info.os, "architecture": "unknown-arch", "path": "bin/other/Game" },
+                { "os": system_os_info.os, "path": "bin/Game" },
+                { "os": system_os_info.os, "path": "bin/Game2" },
+            ],
+        }));
+        assert_eq!(manifest.executable_for_current_platform().as_deref(), Some("bin/Game"));
+    }
+
+    #[test]
+    fn architecture_names_are_normalized() {
+        assert_eq!(normalize_architecture("x86_64"), "x64");
+        assert_eq!(normalize_architecture("AMD64"), "x64");
+        assert_eq!(normalize_architecture("aarch64"), "arm64");
+        assert_eq!(normalize_architecture("i686"), "x86");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn executable_discovery_order_is_deterministic() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(&dir.path().join("a_data/crash_handler"), b"\x7fELF\x02\x01\x01\x00");
+        write_file(&dir.path().join("z_game"), b"\x7fELF\x02\x01\x01\x00");
+        write_file(&dir.path().join("m_game"), b"\x7fELF\x02\x01\x01\x00");
+        write_file(&dir.path().join("b_readme"), b"Read me\n");
+        #[cfg(unix)]
+        for name in ["a_data/crash_handler", "z_game", "m_game", "b_readme"] {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(dir.path().join(name), fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        // Fichiers du dossier triés par nom avant les sous-dossiers
+        let executable = find_executable_in_directory(dir.path()).unwrap();
+        assert_eq!(executable, dir.path().join("m_game").to_string_lossy());
+    }
+
+    #[test]
+    fn deep_link_urls_are_parsed() {
+        assert_eq!(parse_deep_link_url("crzgames://launch/42"), Some(DeepLinkRequest::Launch { game_id: 42 }));
+        assert_eq!(parse_deep_link_url("CRZGAMES://Install/7/?source=web#top"), Some(DeepLinkRequest::Install { game_id: 7 }));
+        assert_eq!(parse_deep_link_url("crzgames://launch/42/extra"), None);
+        assert_eq!(parse_deep_link_url("crzgames://launch/abc"), None);
+        assert_eq!(parse_deep_link_url("crzgames://uninstall/42"), None);
+        assert_eq!(parse_deep_link_url("https://launch/42"), None);
+        assert_eq!(parse_deep_link_url("crz"), None);
+
+        let urls = vec![url::Url::parse("https://example.com").unwrap(), url::Url::parse("crzgames://launch/42").unwrap()];
+        assert_eq!(parse_deep_link_urls(&urls), Some(DeepLinkRequest::Launch { game_id: 42 }));
+    }
+
+    #[test]
+    fn deep_link_arguments_are_parsed() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+
+        assert_eq!(parse_deep_link_args(&args(&["launcher", "crzgames://install/3"])), Some(DeepLinkRequest::Install { game_id: 3 }));
+        assert_eq!(parse_deep_link_args(&args(&["launcher", "--launch", "12"])), Some(DeepLinkRequest::Launch { game_id: 12 }));
+        assert_eq!(parse_deep_link_args(&args(&["launcher", "--minimized", "--install=5"])), Some(DeepLinkRequest::Install { game_id: 5 }));
+        assert_eq!(parse_deep_link_args(&args(&["launcher", "--launch"])), None);
+        // Le premier argument est le chemin de l'exécutable, il n'est pas analysé
+        assert_eq!(parse_deep_link_args(&args(&["crzgames://launch/1"])), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn png_header(size: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        data.extend_from_slice(&size.to_be_bytes());
+        data.extend_from_slice(&size.to_be_bytes());
+        data
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn xdg_user_dirs_are_read_from_home() {
+        let home = tempfile::tempdir().unwrap();
+        let user_dirs = home.path().join(".config/user-dirs.dirs");
+        write_file(&user_dirs, b"# XDG_DESKTOP_DIR=\"$HOME/Ignored\"\nXDG_DOWNLOAD_DIR=\"$HOME/Downloads\"\nXDG_DESKTOP_DIR=\"$HOME/Bureau\"\nXDG_MUSIC_DIR=\"Musique\"\n");
+
+        assert_eq!(read_xdg_user_dir(&user_dirs, "XDG_DESKTOP_DIR", home.path()), Some(home.path().join("Bureau")));
+        assert_eq!(read_xdg_user_dir(&user_dirs, "XDG_MUSIC_DIR", home.path()), None);
+        assert_eq!(read_xdg_user_dir(&user_dirs, "XDG_VIDEOS_DIR", home.path()), None);
+
+        let xdg_dirs = XdgDirs::resolve(home.path(), None, Some("relative/config".into()));
+        assert_eq!(xdg_dirs.desktop_dir, home.path().join("Bureau"));
+        assert_eq!(xdg_dirs.applications_dir(), home.path().join(".local/share/applications"));
+
+        let data_home = home.path().join("data");
+        let xdg_dirs = XdgDirs::resolve(home.path(), Some(data_home.clone().into()), Some(home.path().join("empty").into()));
+        assert_eq!(xdg_dirs.desktop_dir, home.path().join("Desktop"));
+        assert_eq!(xdg_dirs.applications_dir(), data_home.join("applications"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn desktop_exec_arguments_are_quoted() {
+        assert_eq!(desktop_exec_quote("/opt/My Game/game"), "\"/opt/My Game/game\"");
+        assert_eq!(desktop_exec_quote("/games/100%/$run"), "\"/games/100%%/\\\\$run\"");
+        assert_eq!(desktop_exec_quote("a\"b\\c"), "\"a\\\\\"b\\\\\\\\c\"");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_shortcuts_are_created_in_temp_home() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let home = tempfile::tempdir().unwrap();
+        let game = tempfile::tempdir().unwrap();
+        let xdg_dirs = XdgDirs::resolve(home.path(), None, None);
+        let hicolor = home.path().join(".local/share/icons/hicolor");
+
+        // Icône absente : icône par défaut du launcher
+        let created_files = create_linux_shortcuts(&xdg_dirs, "game", "My Game", "\"/opt/game\"", &game.path().join("game.png"), true, true).unwrap();
+        let desktop_file = home.path().join("Desktop/game.desktop");
+        let applications_file = home.path().join(".local/share/applications/game.desktop");
+        let icon_file = hicolor.join("128x128/apps/crzgames-game.png");
+        assert_eq!(created_files, vec![icon_file.clone(), desktop_file.clone(), applications_file.clone()]);
+        assert_eq!(fs::read(&icon_file).unwrap(), DEFAULT_GAME_ICON);
+        let entry = fs::read_to_string(&applications_file).unwrap();
+        assert!(entry.contains("Name=My Game\n") && entry.contains("Exec=\"/opt/game\"\n") && entry.contains("Icon=crzgames-game\n"));
+        assert_ne!(fs::metadata(&desktop_file).unwrap().permissions().mode() & 0o111, 0);
+
+        // Icône d'une taille hors du thème hicolor : dossier 256x256
+        write_file(&game.path().join("game.png"), &png_header(300));
+        let created_files = create_linux_shortcuts(&xdg_dirs, "game", "My Game", "\"/opt/game\"", &game.path().join("game.png"), false, true).unwrap();
+        assert_eq!(created_files, vec![hicolor.join("256x256/apps/crzgames-game.png"), applications_file]);
+
+        write_file(&game.path().join("game.png"), &png_header(64));
+        let created_files = create_linux_shortcuts(&xdg_dirs, "game", "My Game", "\"/opt/game\"", &game.path().join("game.png"), false, false).unwrap();
+        assert_eq!(created_files, vec![hicolor.join("64x64/apps/crzgames-game.png")]);
+    }
+
+    fn local_manifest(game_id: u64, files: &[&str], preserved_paths: &[&str]) -> GameManifestLocal {
+        GameManifestLocal {
+            gameId: game_id,
+            files: files.iter()
+                .map(|name| serde_json::from_value(json!({ "name": name, "hash": "", "size": 0 })).unwrap())
+                .collect(),
+            preservedPaths: preserved_paths.iter().map(|path| path.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cleanup_prunes_untracked_files_at_any_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path();
+        for name in [
+            "game.bin", "data/level1.pak", "data/stray.txt", "data/deep/er/stray.bin", "empty/nested/old.dat",
+            "saves/slot1/save.dat", "config/user.ini", "data/settings.ini", "download.tmp", "manifest_local.json",
+        ] {
+            write_file(&game_dir.join(name), b"x");
+        }
+        fs::create_dir_all(game_dir.join("data/empty_dir")).unwrap();
+        write_file(&game_dir.join(STAGING_DIR_NAME).join("files/game.bin"), b"x");
+
+        let manifest = local_manifest(u64::MAX, &["game.bin", "data/level1.pak"], &["saves/**", "*.ini"]);
+        clean_up_directory(game_dir, &manifest).unwrap();
+
+        for kept in ["game.bin", "data/level1.pak", "saves/slot1/save.dat", "config/user.ini", "data/settings.ini", "manifest_local.json"] {
+            assert!(game_dir.join(kept).exists(), "{} should be kept", kept);
+        }
+        for removed in ["data/stray.txt", "data/deep", "data/empty_dir", "empty", "download.tmp", STAGING_DIR_NAME] {
+            assert!(!game_dir.join(removed).exists(), "{} should be removed", removed);
+        }
+    }
+
+    #[test]
+    fn cleanup_keeps_partial_downloads_while_paused() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path();
+        write_file(&game_dir.join("data/file.tmp"), b"x");
+        write_file(&game_dir.join("data/stray.txt"), b"x");
+        write_file(&game_dir.join(STAGING_DIR_NAME).join("files/game.bin"), b"x");
+
+        let game_id = u64::MAX - 1;
+        pause_download(game_id);
+        clean_up_directory(game_dir, &local_manifest(game_id, &[], &[])).unwrap();
+
+        assert!(game_dir.join("data/file.tmp").exists());
+        assert!(game_dir.join(STAGING_DIR_NAME).join("files/game.bin").exists());
+        assert!(!game_dir.join("data/stray.txt").exists());
+    }
+
+    #[test]
+    fn moved_files_include_previous_version_and_staged_update() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path();
+        write_file(&game_dir.join("game.bin"), b"game");
+        write_file(&game_dir.join("saves/slot1.sav"), b"save");
+        write_file(&game_dir.join("stray.txt"), b"x");
+        write_file(&game_dir.join(PREVIOUS_VERSION_DIR_NAME).join("manifest_local.json"), b"{}");
+        write_file(&game_dir.join(PREVIOUS_VERSION_DIR_NAME).join("files/game.bin"), b"old");
+        write_file(&game_dir.join(STAGING_DIR_NAME).join("files/game.bin"), b"new");
+
+        let manifest = local_manifest(1, &["game.bin"], &["saves/**"]);
+        let files_to_copy = game_files_to_copy(game_dir, &manifest).unwrap();
+        let mut names: Vec<&str> = files_to_copy.iter().map(|(name, _)| name.as_str()).collect();
+        names.sort();
+
+        assert_eq!(names, vec![
+            ".crzgames-previous/files/game.bin",
+            ".crzgames-previous/manifest_local.json",
+            ".crzgames-staging/files/game.bin",
+            "game.bin",
+            "saves/slot1.sav",
+        ]);
+        // Seuls les fichiers du manifeste ont un hash à vérifier après la copie
+        assert!(files_to_copy.iter().all(|(name, hash)| (name == "game.bin") == hash.is_some()));
+    }
+
+    #[test]
+    fn download_stays_active_until_its_guard_is_dropped() {
+        let game_id = u64::MAX - 2;
+        let active_download = ActiveDownloadGuard::new(game_id);
+        assert!(is_download_active(game_id));
+        assert!(!is_download_paused(game_id));
+
+        drop(active_download);
+        assert!(!is_download_active(game_id));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cleanup_does_not_follow_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        write_file(&outside.path().join("user.txt"), b"x");
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("link")).unwrap();
+
+        clean_up_directory(dir.path(), &local_manifest(u64::MAX, &[], &[])).unwrap();
+
+        assert!(outside.path().join("user.txt").exists());
+        assert!(!dir.path().join("link").exists());
+    }
+
+    #[test]
+    fn preserved_path_globs() {
+        let preserved = |path: &str, patterns: &[&str]| {
+            is_preserved_path(path, &patterns.iter().map(|pattern| pattern.to_string()).collect::<Vec<String>>())
+        };
+
+        assert!(preserved("saves/slot1/save.dat", &["saves/**"]));
+        assert!(preserved("saves", &["saves/**"]));
+        assert!(!preserved("data/saves.pak", &["saves/**"]));
+        assert!(preserved("config/user.ini", &["*.ini"]));
+        assert!(preserved("user.ini", &["*.ini"]));
+        assert!(!preserved("user.ini.bak", &["*.ini"]));
+        assert!(preserved("screenshots/2024/shot.png", &["screenshots"]));
+        assert!(preserved("config/user.cfg", &["config/user.cfg"]));
+        assert!(!preserved("other/config/user.cfg", &["config/user.cfg"]));
+        assert!(preserved("a/b/c/logs/today.log", &["**/logs/*.log"]));
+        assert!(preserved("save1.dat", &["save?.dat"]));
+        assert!(!preserved("save10.dat", &["save?.dat"]));
+        assert!(preserved("data\\saves\\slot.dat", &["data/saves/**"]));
+        assert!(!preserved("game.bin", &[]));
+        assert!(!preserved("game.bin", &[""]));
+    }
+
+    #[test]
+    fn glob_names_match_wildcards() {
+        assert!(glob_match_name("*", "anything"));
+        assert!(glob_match_name("*.tar.gz", "pack.tar.gz"));
+        assert!(glob_match_name("a*b*c", "aXXbYYc"));
+        assert!(!glob_match_name("a*b*c", "aXXbYY"));
+        assert!(glob_match_name("Save?", "Save1"));
+        assert!(!glob_match_name("Save?", "Save"));
+    }
+
+    #[test]
+    fn file_hash_is_streamed_sha256() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.bin");
+        write_file(&path, b"abc");
+        assert_eq!(calculate_file_hash(&path).unwrap(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+
+        // Plus grand que le tampon de lecture
+        let content = vec![7u8; 3 * 1024 * 1024 + 17];
+        write_file(&path, &content);
+        assert_eq!(calculate_file_hash(&path).unwrap(), format!("{:x}", Sha256::digest(&content)));
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn verbatim_prefix_is_stripped() {
+        assert_eq!(strip_verbatim_prefix(PathBuf::from(r"\\?\C:\Games\Crz")), PathBuf::from(r"C:\Games\Crz"));
+        assert_eq!(strip_verbatim_prefix(PathBuf::from(r"\\?\UNC\server\share\Games")), PathBuf::from(r"\\server\share\Games"));
+        assert_eq!(strip_verbatim_prefix(PathBuf::from(r"D:\Games")), PathBuf::from(r"D:\Games"));
+    }
+
+    #[test]
+    fn staged_update_recovers_after_partial_switch() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path();
+        let staging_dir = game_dir.join(STAGING_DIR_NAME);
+        write_file(&game_dir.join("data/updated.bin"), b"old");
+        write_file(&game_dir.join("data/kept.bin"), b"kept");
+
+        // Interruption pendant le déplacement des fichiers préparés : ancienne version et fichier
+        // obsolète déjà mis de côté, fichier ajouté par la mise à jour déjà en place
+        write_file(&staging_dir.join("previous/data/updated.bin"), b"old");
+        write_file(&staging_dir.join("previous/data/obsolete.bin"), b"obsolete");
+        write_file(&staging_dir.join("files/data/updated.bin"), b"new");
+        write_file(&game_dir.join("data/added.bin"), b"added");
+
+        let mut manifest = local_manifest(1, &["data/updated.bin", "data/added.bin", "data/kept.bin"], &[]);
+        manifest.version = "2.0.0".to_string();
+        let journal = StagingJournal {
+            manifest,
+            stagedFiles: vec!["data/updated.bin".to_string(), "data/added.bin".to_string()],
+            obsoleteFiles: vec!["data/obsolete.bin".to_string()],
+            previousManifest: None,
+        };
+        write_staging_journal(game_dir, &journal).unwrap();
+
+        assert!(recover_staged_update(game_dir).unwrap());
+        assert_eq!(fs::read(game_dir.join("data/updated.bin")).unwrap(), b"new");
+        assert_eq!(fs::read(game_dir.join("data/added.bin")).unwrap(), b"added");
+        assert_eq!(fs::read(game_dir.join("data/kept.bin")).unwrap(), b"kept");
+        assert!(!game_dir.join("data/obsolete.bin").exists());
+        assert!(!staging_dir.exists());
+        assert_eq!(read_local_manifest(&game_dir.to_string_lossy()).unwrap().unwrap().version, "2.0.0");
+        assert!(!recover_staged_update(game_dir).unwrap());
+    }
+
+    #[test]
+    fn staged_update_promotes_previous_version_last() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path();
+        let staging_dir = game_dir.join(STAGING_DIR_NAME);
+        let previous_version_dir = game_dir.join(PREVIOUS_VERSION_DIR_NAME);
+
+        // Interruption après le déplacement des fichiers mis de côté, avant la suppression du dossier de préparation
+        write_file(&game_dir.join("game.bin"), b"new");
+        write_file(&game_dir.join("added.bin"), b"added");
+        write_file(&previous_version_dir.join("files/game.bin"), b"old");
+        fs::create_dir_all(staging_dir.join("files")).unwrap();
+
+        let mut manifest = local_manifest(1, &["game.bin", "added.bin"], &[]);
+        manifest.version = "2.0.0".to_string();
+        let mut previous_manifest = local_manifest(1, &["game.bin"], &[]);
+        previous_manifest.version = "1.0.0".to_string();
+        let journal = StagingJournal {
+            manifest,
+            stagedFiles: vec!["game.bin".to_string(), "added.bin".to_string()],
+            obsoleteFiles: Vec::new(),
+            previousManifest: Some(previous_manifest),
+        };
+        write_staging_journal(game_dir, &journal).unwrap();
+
+        assert!(recover_staged_update(game_dir).unwrap());
+        assert_eq!(fs::read(game_dir.join("game.bin")).unwrap(), b"new");
+        assert_eq!(fs::read(game_dir.join("added.bin")).unwrap(), b"added");
+        assert_eq!(fs::read(previous_version_dir.join("files/game.bin")).unwrap(), b"old");
+        assert_eq!(read_local_manifest(&previous_version_dir.to_string_lossy()).unwrap().unwrap().version, "1.0.0");
+        assert!(!staging_dir.exists());
+    }
+
+    #[test]
+    fn staged_update_replaces_stale_previous_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path();
+        let previous_version_dir = game_dir.join(PREVIOUS_VERSION_DIR_NAME);
+
+        // Version 0.9.0 gardée par une mise à jour plus ancienne, la mise à jour en 2.0.0 n'ajoute que des fichiers
+        let mut stale_manifest = local_manifest(1, &["game.bin"], &[]);
+        stale_manifest.version = "0.9.0".to_string();
+        reset_previous_version(game_dir, Some(&stale_manifest)).unwrap();
+        write_file(&previous_version_dir.join("files/game.bin"), b"stale");
+        write_file(&game_dir.join("game.bin"), b"current");
+        write_file(&game_dir.join(STAGING_DIR_NAME).join("files/added.bin"), b"added");
+
+        let mut manifest = local_manifest(1, &["game.bin", "added.bin"], &[]);
+        manifest.version = "2.0.0".to_string();
+        let mut previous_manifest = local_manifest(1, &["game.bin"], &[]);
+        previous_manifest.version = "1.0.0".to_string();
+        let journal = StagingJournal {
+            manifest,
+            stagedFiles: vec!["added.bin".to_string()],
+            obsoleteFiles: Vec::new(),
+            previousManifest: Some(previous_manifest),
+        };
+        write_staging_journal(game_dir, &journal).unwrap();
+        switch_staged_update(game_dir, &journal).unwrap();
+
+        assert_eq!(fs::read(game_dir.join("game.bin")).unwrap(), b"current");
+        assert_eq!(fs::read(game_dir.join("added.bin")).unwrap(), b"added");
+        assert!(!previous_version_dir.join("files/game.bin").exists());
+        assert_eq!(read_local_manifest(&previous_version_dir.to_string_lossy()).unwrap().unwrap().version, "1.0.0");
+    }
+
+    #[test]
+    fn channel_access_key_is_encoded_and_not_saved() {
+        let channel = ReleaseChannel { name: "beta".to_string(), accessKey: Some("a&b#c=d e".to_string()) };
+        assert_eq!(channel.access_key_query(), "&accessKey=a%26b%23c%3Dd+e");
+        assert_eq!(ReleaseChannel::default().access_key_query(), "");
+
+        let manifest = GameManifestLocal { channel, ..Default::default() };
+        let saved = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(saved["channel"], json!({ "name": "beta" }));
+    }
+
+    #[test]
+    fn channel_diff_lists_changed_and_obsolete_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path();
+        write_file(&game_dir.join("same.bin"), b"same");
+        write_file(&game_dir.join("changed.bin"), b"old");
+        write_file(&game_dir.join("removed.bin"), b"removed");
+        write_file(&game_dir.join("saves/slot1.sav"), b"save");
+
+        let local_manifest: GameManifestLocal = serde_json::from_value(json!({
+            "pathInstallLocation": game_dir.to_string_lossy(),
+            "gameId": 1,
+            "gameTitle": "Game",
+            "version": "1.0.0",
+            "gameBinarySize": 0,
+            "files": [
+                { "name": "same.bin", "hash": "s", "size": 4 },
+                { "name": "missing.bin", "hash": "m", "size": 7 },
+                { "name": "changed.bin", "hash": "c1", "size": 3 },
+                { "name": "removed.bin", "hash": "r", "size": 7 },
+                { "name": "saves/slot1.sav", "hash": "v", "size": 4 },
+            ],
+        })).unwrap();
+        let remote = remote_manifest(json!({
+            "version": "2.0.0-beta",
+            "files": [
+                { "name": "same.bin", "hash": "s", "size": 4 },
+                { "name": "missing.bin", "hash": "m", "size": 7 },
+                { "name": "changed.bin", "hash": "c2", "size": 5 },
+                { "name": "added.bin", "hash": "a", "size": 10 },
+            ],
+            "preservedPaths": ["saves/**"],
+        }));
+
+        let diff = channel_diff(game_dir, &local_manifest, &remote).unwrap();
+        let mut files_to_download: Vec<_> = diff.filesToDownload.iter().map(|file| file.name.as_str()).collect();
+        files_to_download.sort();
+        assert_eq!(files_to_download, ["added.bin", "changed.bin", "missing.bin"]);
+        assert_eq!(diff.sizeToDownload, 22);
+        assert_eq!(diff.obsoleteFiles, ["removed.bin"]);
+        assert_eq!(diff.sizeToRemove, 7);
+        assert_eq!((diff.currentVersion.as_str(), diff.targetVersion.as_str()), ("1.0.0", "2.0.0-beta"));
+    }
+
+    fn components_manifest() -> GameManifestRemote {
+        remote_manifest(json!({
+            "version": "1.0.0",
+            "files": [
+                { "name": "game.bin", "hash": "g", "size": 1 },
+                { "name": "hd/textures.pak", "hash": "h", "size": 1, "component": "hd" },
+                { "name": "dlc/level.pak", "hash": "d", "size": 1, "component": "dlc" },
+            ],
+            "components": [
+                { "id": "hd", "name": "HD textures", "default": true },
+                { "id": "dlc", "name": "DLC", "dependencies": ["music"] },
+                { "id": "music", "name": "Soundtrack", "dependencies": ["dlc"] },
+            ],
+        }))
+    }
+
+    #[test]
+    fn components_are_resolved_with_dependencies() {
+        let manifest = components_manifest();
+        assert_eq!(manifest.resolve_components(None).unwrap(), ["hd"]);
+        assert_eq!(manifest.resolve_components(Some(&["dlc".to_string()])).unwrap(), ["dlc", "music"]);
+        assert_eq!(manifest.resolve_components(Some(&[])).unwrap(), Vec::<String>::new());
+        assert!(manifest.resolve_components(Some(&["unknown".to_string()])).is_err());
+
+        let files: Vec<_> = manifest.for_components(&["hd".to_string()]).files.into_iter().map(|file| file.name).collect();
+        assert_eq!(files, ["game.bin", "hd/textures.pak"]);
+    }
+
+    #[test]
+    fn installed_components_ignore_removed_components() {
+        let manifest = components_manifest();
+        let mut local = local_manifest(1, &[], &[]);
+        assert_eq!(manifest.installed_components(&local).unwrap(), ["hd"]);
+
+        local.components = Some(vec!["music".to_string(), "legacy".to_string()]);
+        assert_eq!(manifest.installed_components(&local).unwrap(), ["dlc", "music"]);
+
+        local.components = Some(vec!["legacy".to_string()]);
+        assert_eq!(manifest.installed_components(&local).unwrap(), Vec::<String>::new());
+    }
+
+    fn languages_manifest() -> GameManifestRemote {
+        remote_manifest(json!({
+            "version": "1.0.0",
+            "files": [
+                { "name": "game.bin", "hash": "g", "size": 1 },
+                { "name": "lang/fr.pak", "hash": "f", "size": 2, "locales": ["fr-FR"] },
+                { "name": "lang/en.pak", "hash": "e", "size": 3, "locales": ["en-US"] },
+                { "name": "lang/de.pak", "hash": "d", "size": 4, "locales": ["de-DE"] },
+            ],
+            "defaultLanguage": "en-US",
+        }))
+    }
+
+    fn language_file(locales: &[&str]) -> FileDetails {
+        serde_json::from_value(json!({ "name": "file", "hash": "", "size": 0, "locales": locales })).unwrap()
+    }
+
+    #[test]
+    fn files_are_matched_to_languages_ignoring_case() {
+        let french = ["FR-fr".to_string()];
+        assert!(language_file(&["fr-FR"]).is_in_languages(Some(&french)));
+        assert!(!language_file(&["en-US"]).is_in_languages(Some(&french)));
+        assert!(language_file(&[]).is_in_languages(Some(&french)));
+        assert!(language_file(&["en-US"]).is_in_languages(None));
+        assert!(!language_file(&["en-US"]).is_in_languages(Some(&[])));
+    }
+
+    #[test]
+    fn languages_are_resolved_against_the_manifest() {
+        let manifest = languages_manifest();
+        assert_eq!(manifest.available_languages(), ["de-DE", "en-US", "fr-FR"]);
+        assert_eq!(manifest.resolve_languages(None).unwrap(), Some(vec!["en-US".to_string()]));
+        assert_eq!(manifest.resolve_languages(Some(&["fr-fr".to_string()])).unwrap(), Some(vec!["fr-fr".to_string()]));
+        assert!(manifest.resolve_languages(Some(&[])).is_err());
+        assert!(manifest.resolve_languages(Some(&["ja-JP".to_string()])).is_err());
+
+        let all_languages = GameManifestRemote { defaultLanguage: None, ..languages_manifest() };
+        assert_eq!(all_languages.resolve_languages(None).unwrap(), None);
+    }
+
+    #[test]
+    fn installed_languages_drop_languages_removed_by_the_manifest() {
+        let manifest = languages_manifest();
+        let mut local = local_manifest(1, &[], &[]);
+        assert_eq!(manifest.installed_languages(&local).unwrap(), None);
+
+        local.languages = Some(vec!["FR-FR".to_string(), "it-IT".to_string()]);
+        assert_eq!(manifest.installed_languages(&local).unwrap(), Some(vec!["FR-FR".to_string()]));
+
+        local.languages = Some(vec!["it-IT".to_string()]);
+        assert_eq!(manifest.installed_languages(&local).unwrap(), Some(vec!["en-US".to_string()]));
+    }
+
+    #[test]
+    fn imported_languages_are_detected_from_present_files() {
+        let manifest = languages_manifest();
+        let present = |names: &[&str]| -> Vec<FileDetails> {
+            manifest.files.iter().filter(|file| names.contains(&file.name.as_str())).cloned().collect()
+        };
+        assert_eq!(manifest.detect_languages(&present(&["game.bin", "lang/fr.pak"])).unwrap(), Some(vec!["fr-FR".to_string()]));
+        assert_eq!(manifest.detect_languages(&present(&["game.bin"])).unwrap(), Some(vec!["en-US".to_string()]));
+        assert_eq!(manifest.detect_languages(&present(&["lang/fr.pak", "lang/en.pak", "lang/de.pak"])).unwrap(), None);
+    }
+
+    #[test]
+    fn library_folder_is_removed_by_any_path_to_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = dir.path().join("Games");
+        fs::create_dir_all(library.join("sub")).unwrap();
+        let stored = strip_verbatim_prefix(library.canonicalize().unwrap()).to_string_lossy().into_owned();
+
+        let mut library_folders = vec![stored.clone(), "/missing/library".to_string()];
+        forget_library_folder(&mut library_folders, &library.join("sub").join("..").to_string_lossy());
+        assert_eq!(library_folders, ["/missing/library"]);
+
+        forget_library_folder(&mut library_folders, "/missing/library");
+        assert!(library_folders.is_empty());
+    }
+
+    #[test]
+    fn imported_components_are_detected_from_present_files() {
+        let manifest = components_manifest();
+        let present = |names: &[&str]| -> Vec<FileDetails> {
+            manifest.files.iter().filter(|file| names.contains(&file.name.as_str())).cloned().collect()
+        };
+        assert_eq!(manifest.detect_components(&present(&["game.bin", "dlc/level.pak"])).unwrap(), ["dlc", "music"]);
+        assert_eq!(manifest.detect_components(&present(&["game.bin", "hd/textures.pak", "dlc/level.pak"])).unwrap(), ["hd", "dlc", "music"]);
+        assert_eq!(manifest.detect_components(&present(&["game.bin"])).unwrap(), ["hd"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn games_sharing_an_executable_name_keep_their_own_shortcuts() {
+        let home = tempfile::tempdir().unwrap();
+        let game = tempfile::tempdir().unwrap();
+        let xdg_dirs = XdgDirs::resolve(home.path(), None, None);
+        let icon_source_path = game.path().join("Game.png");
+
+        let first_files = create_linux_shortcuts(&xdg_dirs, &linux_desktop_file_name("Game", Some(1)), "First", "\"/games/first/Game\"", &icon_source_path, true, true).unwrap();
+        let second_files = create_linux_shortcuts(&xdg_dirs, &linux_desktop_file_name("Game", Some(2)), "Second", "\"/games/second/Game\"", &icon_source_path, true, true).unwrap();
+        assert!(first_files.iter().all(|file| !second_files.contains(file)));
+        assert!(second_files.contains(&home.path().join(".local/share/icons/hicolor/128x128/apps/crzgames-Game-2.png")));
+
+        // Supprimer les raccourcis du premier jeu ne touche pas à ceux du second
+        for file in &first_files {
+            fs::remove_file(file).unwrap();
+        }
+        assert!(second_files.iter().all(|file| file.exists()));
+        let entry = fs::read_to_string(home.path().join("Desktop/Game-2.desktop")).unwrap();
+        assert!(entry.contains("Name=Second\n") && entry.contains("Icon=crzgames-Game-2\n"));
+    }
+
+    #[test]
+    fn shipped_preserved_file_is_never_replaced_by_an_update() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path();
+        write_file(&game_dir.join("config.ini"), b"edited by the player");
+        write_file(&game_dir.join("game.bin"), b"old");
+
+        let mut installed: GameManifestLocal = serde_json::from_value(json!({
+            "pathInstallLocation": game_dir.to_string_lossy(),
+            "gameId": 1,
+            "gameTitle": "Game",
+            "gameBinarySize": 0,
+            "version": "1.0.0",
+            "files": [
+                { "name": "config.ini", "hash": "c1", "size": 7 },
+                { "name": "game.bin", "hash": "g1", "size": 3 },
+            ],
+            "preservedPaths": ["*.ini"],
+        })).unwrap();
+        let remote = remote_manifest(json!({
+            "version": "2.0.0",
+            "files": [
+                { "name": "config.ini", "hash": "c2", "size": 9 },
+                { "name": "game.bin", "hash": "g2", "size": 3 },
+            ],
+            "preservedPaths": ["*.ini"],
+        }));
+
+        // Mise à jour directe : le fichier reste suivi et n'est pas téléchargé
+        let mut direct_manifest = installed.clone();
+        remove_obsolete_files(game_dir, &mut direct_manifest, &remote, None).unwrap();
+        assert_eq!(direct_manifest.files.iter().map(|file| file.name.as_str()).collect::<Vec<_>>(), ["config.ini"]);
+        assert!(is_kept_preserved_file(game_dir, "config.ini", &remote.preservedPaths));
+        assert!(!is_kept_preserved_file(game_dir, "game.bin", &remote.preservedPaths));
+        assert_eq!(fs::read(game_dir.join("config.ini")).unwrap(), b"edited by the player");
+
+        // Mise à jour préparée : seul game.bin est préparé puis basculé
+        retain_current_files(&mut installed, &remote);
+        assert_eq!(installed.files.iter().map(|file| file.name.as_str()).collect::<Vec<_>>(), ["config.ini"]);
+        write_file(&game_dir.join(STAGING_DIR_NAME).join("files/game.bin"), b"new");
+        let journal = StagingJournal {
+            manifest: installed,
+            stagedFiles: vec!["game.bin".to_string()],
+            obsoleteFiles: Vec::new(),
+            previousManifest: None,
+        };
+        write_staging_journal(game_dir, &journal).unwrap();
+        switch_staged_update(game_dir, &journal).unwrap();
+        assert_eq!(fs::read(game_dir.join("config.ini")).unwrap(), b"edited by the player");
+        assert_eq!(fs::read(game_dir.join("game.bin")).unwrap(), b"new");
+
+        // Modifié par le joueur : pas de réparation demandée avant le lancement
+        let status = check_game_before_launch(game_dir, &journal.manifest, None, true);
+        assert!(matches!(status, PreLaunchStatus::Ok));
+    }
+}